@@ -0,0 +1,95 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Rewriting local home directories, hostnames, and the current username in
+//! content before sharing it, for `--anonymize`.
+
+const USER_PLACEHOLDER: &str = "<user>";
+const HOST_PLACEHOLDER: &str = "<host>";
+
+#[cfg(unix)]
+pub(crate) fn current_username() -> Option<String> {
+    std::env::var("USER").ok()
+}
+
+#[cfg(windows)]
+pub(crate) fn current_username() -> Option<String> {
+    std::env::var("USERNAME").ok()
+}
+
+fn current_hostname() -> Option<String> {
+    gethostname::gethostname().into_string().ok()
+}
+
+/// Replaces the path segment following every occurrence of `prefix` in
+/// `content` with `placeholder`, stopping the segment at the first
+/// character for which `is_boundary` returns `true`.
+fn replace_path_segment(
+    content: &str,
+    prefix: &str,
+    is_boundary: impl Fn(char) -> bool,
+    placeholder: &str,
+) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(index) = rest.find(prefix) {
+        let (before, after_prefix) = rest.split_at(index + prefix.len());
+        output.push_str(before);
+
+        let segment_len = after_prefix
+            .find(&is_boundary)
+            .unwrap_or(after_prefix.len());
+        output.push_str(placeholder);
+        rest = &after_prefix[segment_len..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Rewrites `/home/<user>`, `C:\Users\<user>`, the current hostname, and the
+/// current username throughout `content` to placeholders, so stack traces
+/// and logs can be shared without leaking local identifiers.
+pub fn anonymize(content: &str) -> String {
+    let mut output = replace_path_segment(
+        content,
+        "/home/",
+        |c: char| c == '/' || c.is_whitespace(),
+        USER_PLACEHOLDER,
+    );
+    output = replace_path_segment(
+        &output,
+        r"C:\Users\",
+        |c: char| c == '\\' || c.is_whitespace(),
+        USER_PLACEHOLDER,
+    );
+
+    if let Some(username) = current_username()
+        && !username.is_empty()
+    {
+        output = output.replace(&username, USER_PLACEHOLDER);
+    }
+
+    if let Some(hostname) = current_hostname()
+        && !hostname.is_empty()
+    {
+        output = output.replace(&hostname, HOST_PLACEHOLDER);
+    }
+
+    output
+}
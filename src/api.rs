@@ -0,0 +1,620 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::{Method, Url};
+use reqwest::blocking::{Client, Response as HttpResponse};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, RETRY_AFTER};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error as ThisError;
+
+use crate::vcr::{self, Interaction};
+
+const BASE_URL: &str = "https://www.pastery.net/api/paste/";
+
+/// The number of characters of an unexpected response body to include in an
+/// [`Error::InvalidResponse`] message.
+const BODY_EXCERPT_LEN: usize = 200;
+
+/// An error returned by [`PasteryClient`], distinguishing failure kinds so
+/// that programmatic consumers can match on them instead of parsing error
+/// messages.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// No API key was provided, or pastery.net rejected the one given
+    /// (HTTP 401/403).
+    #[error("invalid API key; check --api-key or the PASTERY_API_KEY environment variable")]
+    Auth,
+
+    /// Pastery.net has no record of the requested paste (HTTP 404), most
+    /// likely because it expired or was deleted.
+    #[error("paste not found or expired; double check the id or URL")]
+    NotFound,
+
+    /// Pastery.net asked us to slow down (HTTP 429).
+    #[error("rate limited by pastery.net{}", .retry_after_secs.map(|s| format!("; retry after {}s", s)).unwrap_or_default())]
+    RateLimited { retry_after_secs: Option<u64> },
+
+    /// The paste content exceeded pastery.net's size limit (HTTP 413).
+    #[error("paste is too large for pastery.net; try splitting it into multiple pastes")]
+    TooLarge,
+
+    /// The HTTP request could not be made at all.
+    #[error("could not reach pastery.net: {0}")]
+    Network(#[from] reqwest::Error),
+
+    /// Pastery.net returned something other than the JSON we expected.
+    #[error("pastery.net returned an invalid response: {0}")]
+    InvalidResponse(String),
+
+    /// Pastery.net returned a well-formed JSON error.
+    #[error("pastery.net returned an error: {0}")]
+    Api(String),
+
+    /// A fetched paste's content did not match what was expected.
+    #[error(
+        "paste `{id}' does not match: fetched {fetched_len} bytes, expected {expected_len} bytes"
+    )]
+    Mismatch {
+        id: String,
+        fetched_len: usize,
+        expected_len: usize,
+    },
+
+    /// A `--record`/`--replay` cassette could not be read, written, or
+    /// matched against.
+    #[error("{0}")]
+    Vcr(String),
+}
+
+/// A snapshot of the pastery.net rate-limit quota, parsed from the
+/// `X-RateLimit-*` response headers, if pastery.net included them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimit {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    pub reset_secs: Option<u64>,
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) => {
+                write!(f, "{}/{} requests remaining", remaining, limit)?
+            }
+            (Some(remaining), None) => write!(f, "{} requests remaining", remaining)?,
+            (None, _) => write!(f, "quota unknown")?,
+        }
+
+        if let Some(reset_secs) = self.reset_secs {
+            write!(f, " (resets in {}s)", reset_secs)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Parses `X-RateLimit-*` headers out of `response`, if present.
+fn extract_rate_limit(response: &HttpResponse) -> Option<RateLimit> {
+    let headers = response.headers();
+
+    let limit = header_value(headers, "x-ratelimit-limit");
+    let remaining = header_value(headers, "x-ratelimit-remaining");
+    let reset_secs = header_value(headers, "x-ratelimit-reset");
+
+    if limit.is_none() && remaining.is_none() && reset_secs.is_none() {
+        None
+    } else {
+        Some(RateLimit {
+            limit,
+            remaining,
+            reset_secs,
+        })
+    }
+}
+
+/// A thin wrapper around the pastery.net API.
+///
+/// Holds a single [`Client`], which pools and keeps its underlying
+/// connections alive across requests. Construct one `PasteryClient` per
+/// invocation and reuse it for every request a command makes, rather than
+/// building a fresh one per upload, so that batch commands like `purge` and
+/// `backup` benefit from connection reuse instead of paying a new TLS
+/// handshake for every paste. `PasteryClient` is `Sync`, so a single
+/// instance can also be shared across threads for concurrent uploads.
+pub struct PasteryClient {
+    http: Client,
+    api_key: String,
+    last_rate_limit: Mutex<Option<RateLimit>>,
+    transport: Transport,
+}
+
+/// Where requests made through a [`PasteryClient`] actually go.
+enum Transport {
+    /// Sent over the network as normal.
+    Live,
+
+    /// Sent over the network, then appended to the cassette at this path.
+    Record(PathBuf),
+
+    /// Served from a cassette instead of touching the network, consuming
+    /// interactions in recording order.
+    Replay(Mutex<VecDeque<Interaction>>),
+}
+
+/// The options used to create a new paste.
+pub struct NewPaste<'a> {
+    pub duration: u32,
+    pub language: &'a str,
+    pub title: Option<String>,
+    pub max_views: Option<u32>,
+}
+
+/// A paste that was just created.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreatedPaste {
+    pub id: String,
+    pub url: String,
+}
+
+/// Summary information about a paste, as returned when listing pastes.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PasteSummary {
+    pub id: String,
+    pub url: String,
+    pub title: Option<String>,
+    pub creation_date: String,
+}
+
+/// The full content and metadata of a paste.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PasteDetails {
+    pub title: Option<String>,
+    pub language: String,
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Response<T> {
+    Ok(T),
+    Error { error_msg: String },
+}
+
+impl<T> Response<T> {
+    fn into_result(self) -> Result<T, Error> {
+        match self {
+            Self::Ok(value) => Ok(value),
+            Self::Error { error_msg } => Err(Error::Api(error_msg)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PasteList {
+    pastes: Vec<PasteSummary>,
+}
+
+/// Parses `response` as pastery's `Response<T>` envelope, first checking the
+/// HTTP status and content type so that an HTML error page or empty body
+/// from pastery.net (or an intermediate proxy) produces a clear error
+/// instead of a confusing JSON parse failure.
+fn parse_response<T: DeserializeOwned>(response: HttpResponse) -> Result<T, Error> {
+    let status = response.status();
+
+    match status.as_u16() {
+        401 | 403 => return Err(Error::Auth),
+        404 => return Err(Error::NotFound),
+        413 => return Err(Error::TooLarge),
+        429 => {
+            let retry_after_secs = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok());
+            return Err(Error::RateLimited { retry_after_secs });
+        }
+        _ => {}
+    }
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+
+    if !status.is_success() || !is_json {
+        let body = response.text().unwrap_or_default();
+        let excerpt: String = body.trim().chars().take(BODY_EXCERPT_LEN).collect();
+
+        return Err(Error::InvalidResponse(if excerpt.is_empty() {
+            format!("HTTP {}", status)
+        } else {
+            format!("HTTP {}: {}", status, excerpt)
+        }));
+    }
+
+    response
+        .json::<Response<T>>()
+        .map_err(|err| Error::InvalidResponse(err.to_string()))?
+        .into_result()
+}
+
+/// Appends `interaction` as one line to the cassette file at `path`, for
+/// `--record`.
+fn record_interaction(path: &Path, interaction: &Interaction) -> Result<(), Error> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| Error::Vcr(format!("could not open cassette `{}': {}", path.display(), err)))?;
+
+    writeln!(file, "{}", vcr::serialize_interaction(interaction))
+        .map_err(|err| Error::Vcr(format!("could not write cassette `{}': {}", path.display(), err)))
+}
+
+/// The `User-Agent` sent when `user_agent` is not overridden, identifying
+/// this client to pastery.net and to any corporate proxy filtering on it.
+const DEFAULT_USER_AGENT: &str = concat!("patisserie/", env!("CARGO_PKG_VERSION"));
+
+/// Forces address family resolution to IPv4 or IPv6 only, for `-4`/`-6`,
+/// since broken IPv6 at some sites makes every connection hang for several
+/// seconds before falling back to IPv4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+/// Connection tuning for [`PasteryClient::new`], surfaced as config knobs
+/// for users on flaky satellite/VPN links where the defaults perform
+/// poorly.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConnectionOptions<'a> {
+    /// Overrides the default `User-Agent` header.
+    pub user_agent: Option<&'a str>,
+
+    /// Forces HTTP/1.1 instead of negotiating HTTP/2, for links where
+    /// HTTP/2's multiplexing performs worse than plain keep-alive.
+    pub http1_only: bool,
+
+    /// How often to send TCP keepalive probes on idle connections.
+    pub tcp_keepalive: Option<Duration>,
+
+    /// How long a pooled idle connection is kept open before being closed.
+    pub pool_idle_timeout: Option<Duration>,
+
+    /// Restricts connections to one address family.
+    pub ip_version: Option<IpVersion>,
+
+    /// Overrides DNS resolution for `host:port` to a specific address, as
+    /// with curl's `--resolve`, for testing against a staging deployment or
+    /// working around split DNS.
+    pub resolve_overrides: &'a [(String, SocketAddr)],
+
+    /// Appends every API interaction to this cassette file, for `--record`.
+    /// Ignored if `replay` is also set.
+    pub record: Option<&'a Path>,
+
+    /// Serves API interactions from this already-parsed cassette instead of
+    /// making real requests, for `--replay`.
+    pub replay: Option<&'a [Interaction]>,
+}
+
+impl PasteryClient {
+    /// Builds a client backed by a single connection-pooling [`Client`].
+    pub fn new(api_key: String, connection: ConnectionOptions<'_>) -> Self {
+        let mut builder =
+            Client::builder().user_agent(connection.user_agent.unwrap_or(DEFAULT_USER_AGENT));
+
+        if connection.http1_only {
+            builder = builder.http1_only();
+        }
+        if let Some(tcp_keepalive) = connection.tcp_keepalive {
+            builder = builder.tcp_keepalive(tcp_keepalive);
+        }
+        if let Some(pool_idle_timeout) = connection.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(ip_version) = connection.ip_version {
+            let local_address = match ip_version {
+                IpVersion::V4 => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                IpVersion::V6 => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            };
+            builder = builder.local_address(local_address);
+        }
+        for (domain, addr) in connection.resolve_overrides {
+            builder = builder.resolve(domain, *addr);
+        }
+
+        let http = builder
+            .build()
+            .expect("the TLS backend should always initialize successfully");
+
+        let transport = if let Some(interactions) = connection.replay {
+            Transport::Replay(Mutex::new(interactions.iter().cloned().collect()))
+        } else if let Some(path) = connection.record {
+            Transport::Record(path.to_owned())
+        } else {
+            Transport::Live
+        };
+
+        Self {
+            http,
+            api_key,
+            last_rate_limit: Mutex::new(None),
+            transport,
+        }
+    }
+
+    /// The rate-limit quota observed in the most recent response, if
+    /// pastery.net included `X-RateLimit-*` headers in it.
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self.last_rate_limit.lock().unwrap()
+    }
+
+    /// Sends a request through this client's transport: live, recorded to a
+    /// cassette as it goes, or replayed from one without touching the
+    /// network.
+    fn send(&self, method: Method, url: Url, body: Option<String>) -> Result<HttpResponse, Error> {
+        if let Transport::Replay(cassette) = &self.transport {
+            let mut cassette = cassette.lock().unwrap();
+            let interaction = vcr::next_match(&mut cassette, &method, &url).ok_or_else(|| {
+                Error::Vcr(format!(
+                    "no recorded interaction for {} {} in the replay cassette",
+                    method, url
+                ))
+            })?;
+            return Ok(vcr::to_http_response(&interaction));
+        }
+
+        let mut request = self.http.request(method.clone(), url.clone());
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        let response = request.send()?;
+
+        if let Transport::Record(path) = &self.transport {
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = response.bytes()?.to_vec();
+
+            let interaction = vcr::capture(&method, &url, status, &headers, &body);
+            record_interaction(path, &interaction)?;
+
+            return Ok(vcr::to_http_response(&interaction));
+        }
+
+        Ok(response)
+    }
+
+    fn record_rate_limit(&self, response: &HttpResponse) {
+        if let Some(rate_limit) = extract_rate_limit(response) {
+            *self.last_rate_limit.lock().unwrap() = Some(rate_limit);
+        }
+    }
+
+    /// Creates a new paste with the given content.
+    pub fn create(&self, body: String, opts: NewPaste<'_>) -> Result<CreatedPaste, Error> {
+        let mut url = Url::parse(BASE_URL).unwrap();
+        {
+            let mut query = url.query_pairs_mut();
+
+            query
+                .append_pair("api_key", &self.api_key)
+                .append_pair("duration", &opts.duration.to_string())
+                .append_pair("language", opts.language);
+
+            if let Some(title) = &opts.title {
+                query.append_pair("title", title);
+            }
+
+            if let Some(max_views) = opts.max_views {
+                query.append_pair("max_views", &max_views.to_string());
+            }
+        }
+
+        let response = self.send(Method::POST, url, Some(body))?;
+        self.record_rate_limit(&response);
+        parse_response(response)
+    }
+
+    /// Fetches the content and metadata of a single paste.
+    pub fn get(&self, id: &str) -> Result<PasteDetails, Error> {
+        let mut url = Url::parse(BASE_URL)
+            .unwrap()
+            .join(&format!("{}/", id))
+            .unwrap();
+        url.query_pairs_mut().append_pair("api_key", &self.api_key);
+
+        let response = self.send(Method::GET, url, None)?;
+        self.record_rate_limit(&response);
+        parse_response(response)
+    }
+
+    /// Lists every paste on the account.
+    pub fn list(&self) -> Result<Vec<PasteSummary>, Error> {
+        let mut url = Url::parse(BASE_URL).unwrap().join("list/").unwrap();
+        url.query_pairs_mut().append_pair("api_key", &self.api_key);
+
+        let response = self.send(Method::GET, url, None)?;
+        self.record_rate_limit(&response);
+        parse_response::<PasteList>(response).map(|list| list.pastes)
+    }
+
+    /// Deletes a paste by id.
+    pub fn delete(&self, id: &str) -> Result<(), Error> {
+        let mut url = Url::parse(BASE_URL)
+            .unwrap()
+            .join(&format!("{}/delete/", id))
+            .unwrap();
+        url.query_pairs_mut().append_pair("api_key", &self.api_key);
+
+        let response = self.send(Method::GET, url, None)?;
+        self.record_rate_limit(&response);
+        parse_response::<serde::de::IgnoredAny>(response).map(|_| ())
+    }
+}
+
+/// Fetches a paste and compares its body byte-for-byte with `expected`.
+///
+/// Returns `Ok(())` if they match, or [`Error::Mismatch`] otherwise.
+pub fn verify(client: &PasteryClient, id: &str, expected: &str) -> Result<(), Error> {
+    let fetched = client.get(id)?;
+
+    if fetched.body == expected {
+        Ok(())
+    } else {
+        Err(Error::Mismatch {
+            id: id.to_owned(),
+            fetched_len: fetched.body.len(),
+            expected_len: expected.len(),
+        })
+    }
+}
+
+/// Derives the direct raw-text URL for a paste from its HTML page URL.
+pub fn raw_url(url: &str) -> String {
+    format!("{}/raw/", url.trim_end_matches('/'))
+}
+
+/// Extracts a paste id from a pastery URL or bare id.
+///
+/// Pastery URLs look like `https://www.pastery.net/<id>/`, so the id is the
+/// last non-empty path segment. If `reference` does not look like a URL, it
+/// is assumed to already be a bare id.
+pub fn extract_id(reference: &str) -> &str {
+    if !reference.contains("://") {
+        return reference;
+    }
+
+    reference
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)], body: &str) -> HttpResponse {
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        HttpResponse::from(builder.body(body.as_bytes().to_vec()).unwrap())
+    }
+
+    #[test]
+    fn maps_401_and_403_to_auth() {
+        assert!(matches!(
+            parse_response::<CreatedPaste>(response(401, &[], "")),
+            Err(Error::Auth)
+        ));
+        assert!(matches!(
+            parse_response::<CreatedPaste>(response(403, &[], "")),
+            Err(Error::Auth)
+        ));
+    }
+
+    #[test]
+    fn maps_404_to_not_found() {
+        assert!(matches!(
+            parse_response::<CreatedPaste>(response(404, &[], "")),
+            Err(Error::NotFound)
+        ));
+    }
+
+    #[test]
+    fn maps_413_to_too_large() {
+        assert!(matches!(
+            parse_response::<CreatedPaste>(response(413, &[], "")),
+            Err(Error::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn maps_429_to_rate_limited_with_retry_after() {
+        let err = parse_response::<CreatedPaste>(response(429, &[("retry-after", "30")], ""))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::RateLimited {
+                retry_after_secs: Some(30)
+            }
+        ));
+    }
+
+    #[test]
+    fn non_json_response_is_an_invalid_response_with_a_status_excerpt() {
+        let err = parse_response::<CreatedPaste>(response(
+            502,
+            &[("content-type", "text/html")],
+            "<html><body>Bad Gateway</body></html>",
+        ))
+        .unwrap_err();
+
+        match err {
+            Error::InvalidResponse(message) => {
+                assert!(message.contains("502"));
+                assert!(message.contains("Bad Gateway"));
+            }
+            other => panic!("expected InvalidResponse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_a_successful_json_response() {
+        let paste = parse_response::<CreatedPaste>(response(
+            200,
+            &[("content-type", "application/json")],
+            r#"{"id":"abc123","url":"https://www.pastery.net/abc123/"}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(paste.id, "abc123");
+        assert_eq!(paste.url, "https://www.pastery.net/abc123/");
+    }
+
+    #[test]
+    fn parses_a_well_formed_json_error() {
+        let err = parse_response::<CreatedPaste>(response(
+            200,
+            &[("content-type", "application/json")],
+            r#"{"error_msg":"something went wrong"}"#,
+        ))
+        .unwrap_err();
+
+        assert!(matches!(err, Error::Api(message) if message == "something went wrong"));
+    }
+}
@@ -0,0 +1,185 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! An optional local archive of every upload's content, so it outlives the
+//! paste's expiry on pastery.net.
+//!
+//! Content is stored by BLAKE3 hash under `objects/`, so pasting the same
+//! content twice (CI logs, boilerplate configs) only stores it once. An
+//! `index.jsonl` alongside it records which paste id maps to which hash, in
+//! the same append-only style as [`crate::history`].
+//!
+//! If [`crypto::passphrase`] returns a passphrase, archived content is
+//! encrypted at rest with it; otherwise it is stored as plain text, exactly
+//! as it was uploaded. A one-byte tag at the head of each object records
+//! which of the two it is, so [`retrieve`] can tell without needing to know
+//! whether a passphrase was in use when the object was written.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use anyhow::{Context, anyhow};
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto;
+
+const TAG_PLAIN: u8 = 0;
+const TAG_ENCRYPTED: u8 = 1;
+
+/// An `id -> hash` mapping, recorded after the fact, the same way
+/// [`crate::history::HistoryEntry`] maps a content hash back to a paste.
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    id: String,
+    hash: String,
+}
+
+fn dir() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("archive")).ok()
+}
+
+fn content_hash(content: &[u8]) -> String {
+    blake3::hash(content).to_hex().to_string()
+}
+
+/// Writes `content` to the local archive under its content hash, creating
+/// the archive directory if needed, and records that `id` maps to it.
+/// Encrypts the content first if [`crypto::passphrase`] is set. If the same
+/// content has already been archived, the existing object is reused.
+pub fn store(id: &str, content: &str) -> Result<(), anyhow::Error> {
+    let Some(dir) = dir() else {
+        return Ok(());
+    };
+
+    let objects_dir = dir.join("objects");
+    fs::create_dir_all(&objects_dir)
+        .with_context(|| format!("Could not create directory `{}'", objects_dir))?;
+
+    let hash = content_hash(content.as_bytes());
+    let object_path = objects_dir.join(&hash);
+
+    if !object_path.exists() {
+        let bytes = match crypto::passphrase() {
+            Some(passphrase) => {
+                let mut bytes = vec![TAG_ENCRYPTED];
+                bytes.extend(crypto::encrypt(&passphrase, content.as_bytes())?);
+                bytes
+            }
+            None => {
+                let mut bytes = vec![TAG_PLAIN];
+                bytes.extend_from_slice(content.as_bytes());
+                bytes
+            }
+        };
+
+        fs::write(&object_path, &bytes)
+            .with_context(|| format!("Could not write file `{}'", object_path))?;
+    }
+
+    append_index(
+        &dir,
+        &IndexEntry {
+            id: id.to_owned(),
+            hash,
+        },
+    )
+}
+
+fn append_index(dir: &Utf8PathBuf, entry: &IndexEntry) -> Result<(), anyhow::Error> {
+    let path = dir.join("index.jsonl");
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Could not open file `{}' for writing", path))?;
+
+    let line = serde_json::to_string(entry).context("Could not serialize archive index entry")?;
+    writeln!(file, "{}", line).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
+
+/// Finds the most recent archive hash recorded for `id`, if any.
+fn find_hash(dir: &Utf8PathBuf, id: &str) -> Result<Option<String>, anyhow::Error> {
+    let path = dir.join("index.jsonl");
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("Could not read file `{}'", path)),
+    };
+
+    let mut found = None;
+    for line in contents.lines() {
+        let entry: IndexEntry = serde_json::from_str(line)
+            .with_context(|| format!("Could not parse archive index entry `{}'", line))?;
+        if entry.id == id {
+            found = Some(entry.hash);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Reads and, if necessary, decrypts the archived content for `id`, or
+/// returns `Ok(None)` if nothing has been archived under that id.
+pub fn retrieve(id: &str) -> Result<Option<String>, anyhow::Error> {
+    let Some(dir) = dir() else {
+        return Ok(None);
+    };
+
+    let Some(hash) = find_hash(&dir, id)? else {
+        return Ok(None);
+    };
+
+    let object_path = dir.join("objects").join(&hash);
+    let bytes =
+        fs::read(&object_path).with_context(|| format!("Could not read file `{}'", object_path))?;
+
+    let (&tag, rest) = bytes
+        .split_first()
+        .ok_or_else(|| anyhow!("Archive object `{}' is empty", object_path))?;
+
+    let plaintext = match tag {
+        TAG_PLAIN => rest.to_vec(),
+        TAG_ENCRYPTED => {
+            let passphrase = crypto::passphrase().ok_or_else(|| {
+                anyhow!(
+                    "Archive object `{}' is encrypted; set {} to decrypt it",
+                    object_path,
+                    crypto::PASSPHRASE_ENV_VAR
+                )
+            })?;
+            crypto::decrypt(&passphrase, rest)?
+        }
+        tag => {
+            return Err(anyhow!(
+                "Archive object `{}' has unknown format tag {}",
+                object_path,
+                tag
+            ));
+        }
+    };
+
+    String::from_utf8(plaintext)
+        .with_context(|| format!("Archived content for `{}' is not valid UTF-8", id))
+        .map(Some)
+}
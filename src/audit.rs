@@ -0,0 +1,86 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! An opt-in, append-only log of paste operations, for compliance-minded
+//! teams that need a record of who did what to which paste and when before
+//! they can allow a paste CLI at all. Off unless `audit_log_path` is set.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use anyhow::Context;
+use camino::Utf8Path;
+use serde::Serialize;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::anonymize::current_username;
+use crate::config::Config;
+
+/// One recorded operation, appended as a single JSON line.
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    who: String,
+    what: &'a str,
+    when: String,
+    url: &'a str,
+    size: Option<usize>,
+    sha256: Option<&'a str>,
+}
+
+/// Appends a record of `what` (e.g. `"paste"`, `"get"`, or `"renew"`)
+/// happening to the paste at `url` to the log configured at
+/// `audit_log_path`, along with its `size` and `sha256`, if known for this
+/// kind of operation. A no-op if no path is configured.
+pub fn record(
+    config: &Config,
+    what: &str,
+    url: &str,
+    size: Option<usize>,
+    sha256: Option<&str>,
+) -> Result<(), anyhow::Error> {
+    let Some(path) = &config.audit_log_path else {
+        return Ok(());
+    };
+    let path = Utf8Path::new(path);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create directory `{}'", dir))?;
+    }
+
+    let entry = AuditEntry {
+        who: current_username().unwrap_or_else(|| "unknown".to_owned()),
+        what,
+        when: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .expect("the current time is always representable as RFC 3339"),
+        url,
+        size,
+        sha256,
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Could not open file `{}' for writing", path))?;
+
+    let line = serde_json::to_string(&entry).context("Could not serialize audit entry")?;
+    writeln!(file, "{}", line).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! `cargo-paste`: an optional `cargo` subcommand, built with `--features
+//! cargo-paste`, that runs another cargo command and pastes its combined
+//! output, so a build or test failure can be shared with `cargo paste
+//! build` instead of copying terminal output by hand.
+//!
+//! This binary is intentionally minimal: it only depends on the
+//! `patisserie` library crate, not the full CLI's config file, filters, or
+//! notifications, so it stays a thin, single-purpose shim.
+
+use std::env;
+use std::process::Command;
+
+use patisserie::api::{ConnectionOptions, NewPaste, PasteryClient};
+
+const API_KEY_ENV_VAR: &str = "PASTERY_API_KEY";
+const DEFAULT_DURATION_MINUTES: u32 = 24 * 60;
+const DEFAULT_LANGUAGE: &str = "rust";
+
+fn main() {
+    std::process::exit(run());
+}
+
+fn run() -> i32 {
+    // `cargo paste build` invokes this binary as `cargo-paste paste
+    // build`, so the leading `paste` (cargo's own subcommand name) is
+    // dropped if present, letting the binary also be run directly.
+    let mut cargo_args: Vec<String> = env::args().skip(1).collect();
+    if cargo_args.first().is_some_and(|arg| arg == "paste") {
+        cargo_args.remove(0);
+    }
+    if cargo_args.is_empty() {
+        cargo_args.push("build".to_owned());
+    }
+
+    let api_key = match env::var(API_KEY_ENV_VAR) {
+        Ok(api_key) => api_key,
+        Err(_) => {
+            eprintln!("Error: {} is not set", API_KEY_ENV_VAR);
+            return 1;
+        }
+    };
+
+    let output = match Command::new("cargo").args(&cargo_args).output() {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!(
+                "Error: could not run `cargo {}': {}",
+                cargo_args.join(" "),
+                err
+            );
+            return 1;
+        }
+    };
+
+    let mut body = String::from_utf8_lossy(&output.stdout).into_owned();
+    body.push_str(&String::from_utf8_lossy(&output.stderr));
+    print!("{}", body);
+
+    let client = PasteryClient::new(api_key, ConnectionOptions::default());
+    match client.create(
+        body,
+        NewPaste {
+            duration: DEFAULT_DURATION_MINUTES,
+            language: DEFAULT_LANGUAGE,
+            title: Some(format!("cargo {}", cargo_args.join(" "))),
+            max_views: None,
+        },
+    ) {
+        Ok(paste) => eprintln!("\nPasted: {}", paste.url),
+        Err(err) => {
+            eprintln!("Error: could not upload paste: {}", err);
+            return 1;
+        }
+    }
+
+    output.status.code().unwrap_or(1)
+}
@@ -0,0 +1,72 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Opening a paste's URL in a browser with `--open`, so it doesn't have to
+//! be copied and pasted by hand.
+//!
+//! A missing or unreachable browser is reported as a warning rather than
+//! failing the upload it is only meant to announce.
+
+use std::process::Command;
+
+/// Opens `url` in `browser` if given (a browser command name, e.g.
+/// `firefox`), the browser named by the `$BROWSER` environment variable if
+/// that's set, or the system default browser otherwise.
+///
+/// The system default isn't always the browser logged into pastery, which
+/// is why `browser` (and `$BROWSER`) can override it.
+pub fn open(url: &str, browser: Option<&str>) {
+    match browser
+        .map(str::to_owned)
+        .or_else(|| std::env::var("BROWSER").ok())
+    {
+        Some(browser) => run(&browser, &[url]),
+        None => open_with_system_default(url),
+    }
+}
+
+fn run(program: &str, args: &[&str]) {
+    match Command::new(program).args(args).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "{}",
+            crate::color::warning(&format!("Warning: `{}' exited with {}", program, status))
+        ),
+        Err(err) => eprintln!(
+            "{}",
+            crate::color::warning(&format!("Warning: could not run `{}': {}", program, err))
+        ),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn open_with_system_default(url: &str) {
+    run("open", &[url]);
+}
+
+#[cfg(target_os = "windows")]
+fn open_with_system_default(url: &str) {
+    // `start` is a `cmd` builtin, not its own executable; the empty
+    // argument is the window title `start` otherwise mistakes a quoted URL
+    // for.
+    run("cmd", &["/c", "start", "", url]);
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn open_with_system_default(url: &str) {
+    run("xdg-open", &[url]);
+}
@@ -0,0 +1,207 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Loading a paste's URL into a clipboard with `--copy` (including the
+//! Windows clipboard when running under WSL), or a paste buffer outside the
+//! system clipboard, e.g. tmux's, with `--tmux`.
+//!
+//! A missing or unreachable clipboard mechanism is reported as a warning
+//! rather than failing the upload it is only meant to announce.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::anyhow;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+/// A clipboard mechanism `--copy` can use, either detected automatically or
+/// pinned with the `clipboard_backend` config setting.
+#[derive(Clone, Copy)]
+pub enum Backend {
+    Wayland,
+    X11,
+    Osc52,
+    Tmux,
+    Windows,
+    Macos,
+}
+
+impl Backend {
+    /// Parses a `clipboard_backend` config value.
+    pub fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        match s {
+            "wayland" => Ok(Self::Wayland),
+            "x11" => Ok(Self::X11),
+            "osc52" => Ok(Self::Osc52),
+            "tmux" => Ok(Self::Tmux),
+            "windows" => Ok(Self::Windows),
+            "macos" => Ok(Self::Macos),
+            _ => Err(anyhow!(
+                "Expected `clipboard_backend' to be one of `wayland', `x11', `osc52', `tmux', \
+                 `windows', or `macos', got `{}'",
+                s
+            )),
+        }
+    }
+
+    /// Detects which backend to use based on the environment: the Windows
+    /// clipboard under WSL, tmux or Wayland/X11 on Linux, or the platform
+    /// clipboard on macOS and Windows, falling back to OSC 52 if nothing
+    /// more specific is available.
+    ///
+    /// Auto-detection guesses wrong inside nested SSH/tmux setups, which is
+    /// why `clipboard_backend` exists to pin it instead.
+    fn detect() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Macos
+        } else if cfg!(target_os = "windows") || is_wsl() {
+            Self::Windows
+        } else if std::env::var_os("TMUX").is_some() {
+            Self::Tmux
+        } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Self::Wayland
+        } else if std::env::var_os("DISPLAY").is_some() {
+            Self::X11
+        } else {
+            Self::Osc52
+        }
+    }
+}
+
+/// Detects whether we are running under WSL, where the browser the paste's
+/// URL matters to is on the Windows side, not the Linux one.
+fn is_wsl() -> bool {
+    std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/version")
+            .is_ok_and(|version| version.to_lowercase().contains("microsoft"))
+}
+
+/// Copies `text` to the clipboard, using `backend` if given, or detecting
+/// one automatically otherwise.
+pub fn copy(backend: Option<Backend>, text: &str) {
+    match backend.unwrap_or_else(Backend::detect) {
+        Backend::Wayland => pipe_to_command("wl-copy", &[], text),
+        Backend::X11 => pipe_to_command("xclip", &["-selection", "clipboard"], text),
+        Backend::Tmux => set_tmux_buffer(text),
+        Backend::Windows => set_windows_clipboard(text),
+        Backend::Macos => pipe_to_command("pbcopy", &[], text),
+        Backend::Osc52 => print_osc52(text),
+    }
+}
+
+/// Sets the Windows clipboard to `text`.
+///
+/// On Windows itself this talks to the clipboard directly through the
+/// `clipboard-win` crate, rather than shelling out to `clip.exe`. Outside
+/// Windows (e.g. inside WSL, where `Backend::Windows` is used to reach the
+/// host clipboard) we shell out to `clip.exe`, falling back to
+/// `powershell.exe` if `clip.exe` isn't on the `PATH`.
+#[cfg(windows)]
+fn set_windows_clipboard(text: &str) {
+    if let Err(err) = clipboard_win::set_clipboard_string(text) {
+        eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "Warning: could not set the Windows clipboard: {}",
+                err
+            ))
+        );
+    }
+}
+
+#[cfg(not(windows))]
+fn set_windows_clipboard(text: &str) {
+    if try_pipe_to_command("clip.exe", &[], text).is_err() {
+        pipe_to_command(
+            "powershell.exe",
+            &[
+                "-NoProfile",
+                "-Command",
+                "Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+            ],
+            text,
+        );
+    }
+}
+
+/// Runs `tmux set-buffer` with `text`.
+pub fn set_tmux_buffer(text: &str) {
+    let result = Command::new("tmux")
+        .args(["set-buffer", "--", text])
+        .status();
+
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "Warning: `tmux set-buffer' exited with {}",
+                status
+            ))
+        ),
+        Err(err) => eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "Warning: could not run `tmux set-buffer': {}",
+                err
+            ))
+        ),
+    }
+}
+
+/// Runs `program` with `args`, writing `text` to its standard input, for
+/// clipboard tools like `wl-copy`, `xclip`, `pbcopy`, and `clip.exe` that
+/// read the clipboard contents from stdin, printing a warning on failure.
+fn pipe_to_command(program: &str, args: &[&str], text: &str) {
+    if let Err(err) = try_pipe_to_command(program, args, text) {
+        eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "Warning: could not run `{}' to copy to the clipboard: {}",
+                program, err
+            ))
+        );
+    }
+}
+
+/// Like [`pipe_to_command`], but returns the underlying error instead of
+/// warning about it, so callers with a fallback can try something else
+/// first.
+fn try_pipe_to_command(program: &str, args: &[&str], text: &str) -> std::io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin was piped")
+        .write_all(text.as_bytes())?;
+    child.wait()?;
+
+    Ok(())
+}
+
+/// Writes `text` to the clipboard via an OSC 52 escape sequence, which most
+/// modern terminal emulators forward to the system clipboard even over SSH,
+/// without needing an external clipboard tool.
+fn print_osc52(text: &str) {
+    print!("\x1b]52;c;{}\x07", BASE64.encode(text));
+    let _ = std::io::stdout().flush();
+}
@@ -0,0 +1,102 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Local collections: named groups of related pastes, registered with
+//! `patisserie collection create` and populated with `paste --collection`.
+//!
+//! A collection is just a well-known tag that must be registered before
+//! use, so a typo in `--collection` fails loudly instead of silently
+//! creating a new, disconnected group.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use anyhow::{Context, anyhow};
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// A collection registered with `patisserie collection create`.
+#[derive(Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    pub created_at: String,
+}
+
+fn path() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("collections.jsonl")).ok()
+}
+
+/// Lists every registered collection, in creation order.
+pub fn list() -> Result<Vec<Collection>, anyhow::Error> {
+    let Some(path) = path() else {
+        return Ok(Vec::new());
+    };
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Could not read file `{}'", path)),
+    };
+
+    contents
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Could not parse collection entry `{}'", line))
+        })
+        .collect()
+}
+
+/// Whether a collection named `name` has been registered with `create`.
+pub fn exists(name: &str) -> Result<bool, anyhow::Error> {
+    Ok(list()?.iter().any(|collection| collection.name == name))
+}
+
+/// Registers a new, empty collection named `name`, failing if one with that
+/// name already exists.
+pub fn create(name: &str) -> Result<(), anyhow::Error> {
+    if exists(name)? {
+        return Err(anyhow!("Collection `{}' already exists", name));
+    }
+
+    let path = path().context("Could not determine a data directory for collections")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create directory `{}'", dir))?;
+    }
+
+    let entry = Collection {
+        name: name.to_owned(),
+        created_at: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .expect("the current time is always representable as RFC 3339"),
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Could not open file `{}' for writing", path))?;
+
+    let line = serde_json::to_string(&entry).context("Could not serialize collection entry")?;
+    writeln!(file, "{}", line).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Colorizing success, error, warning, and URL output for `--color`, so it
+//! stands out among the surrounding terminal output.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+
+/// When to colorize output, as given to `--color`.
+#[derive(Clone, Copy, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Parses a `--color` value.
+    pub fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(anyhow!(
+                "Expected one of `auto', `always', or `never', got `{}'",
+                s
+            )),
+        }
+    }
+}
+
+static CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// Remembers `choice` for the `success`/`error`/`warning`/`url` helpers used
+/// throughout the program. Should be called once, early in `main`.
+pub fn init(choice: ColorChoice) {
+    let _ = CHOICE.set(choice);
+}
+
+/// Whether output written to a stream should be colorized, given whether
+/// that stream is itself a terminal.
+///
+/// `NO_COLOR` is honored for `--color auto` (the default), so output piped
+/// into another command or a log file isn't cluttered with escape codes.
+fn enabled(stream_is_terminal: bool) -> bool {
+    match CHOICE.get().copied().unwrap_or_default() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => std::env::var_os("NO_COLOR").is_none() && stream_is_terminal,
+    }
+}
+
+fn paint(code: &str, text: &str, stream_is_terminal: bool) -> String {
+    if enabled(stream_is_terminal) {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Colors `text` green, for standard-output success messages.
+pub fn success(text: &str) -> String {
+    paint("32", text, std::io::stdout().is_terminal())
+}
+
+/// Colors `text` red, for standard-error error messages.
+pub fn error(text: &str) -> String {
+    paint("31", text, std::io::stderr().is_terminal())
+}
+
+/// Colors `text` yellow, for standard-error warning messages.
+pub fn warning(text: &str) -> String {
+    paint("33", text, std::io::stderr().is_terminal())
+}
+
+/// Colors `text` bold cyan, to highlight a paste URL printed to standard
+/// output.
+pub fn url(text: &str) -> String {
+    paint("1;36", text, std::io::stdout().is_terminal())
+}
+
+/// Colors `text` bold yellow, to highlight a search match printed to
+/// standard output, in the style of `grep --color`.
+pub fn highlight_match(text: &str) -> String {
+    paint("1;33", text, std::io::stdout().is_terminal())
+}
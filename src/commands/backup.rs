@@ -0,0 +1,114 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::porcelain;
+use crate::throttle::Throttle;
+use patisserie::api::PasteryClient;
+
+/// Download every paste on the account into a local directory.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The directory to write pastes into. Created if it does not exist.
+    directory: Utf8PathBuf,
+
+    /// Print a stable, tab-separated line (id, content_path) per paste
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+
+    /// The maximum number of paste-fetch requests to make per minute.
+    ///
+    /// If not provided, this falls back to the `requests_per_minute` set in
+    /// the config file, if any. Otherwise, fetches are not throttled.
+    #[arg(long)]
+    requests_per_minute: Option<u32>,
+}
+
+/// The metadata written alongside each paste's content during a backup.
+#[derive(Serialize)]
+struct Metadata<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: Option<&'a str>,
+    language: &'a str,
+    creation_date: &'a str,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    fs::create_dir_all(&args.directory)
+        .with_context(|| format!("Could not create directory `{}'", args.directory))?;
+
+    let pastes = client.list().context("Could not list pastes")?;
+
+    let mut throttle = Throttle::new(args.requests_per_minute.or(config.requests_per_minute));
+
+    for summary in &pastes {
+        throttle.wait();
+        let details = client
+            .get(&summary.id)
+            .with_context(|| format!("Could not fetch paste `{}'", summary.id))?;
+
+        let content_path = args.directory.join(format!("{}.txt", summary.id));
+        fs::write(&content_path, &details.body)
+            .with_context(|| format!("Could not write file `{}'", content_path))?;
+
+        let metadata = Metadata {
+            id: &summary.id,
+            url: &summary.url,
+            title: details.title.as_deref(),
+            language: &details.language,
+            creation_date: &summary.creation_date,
+        };
+
+        let metadata_path = args.directory.join(format!("{}.json", summary.id));
+        let metadata_json = serde_json::to_string_pretty(&metadata)
+            .context("Could not serialize paste metadata")?;
+        fs::write(&metadata_path, metadata_json)
+            .with_context(|| format!("Could not write file `{}'", metadata_path))?;
+
+        if args.porcelain {
+            println!("{}", porcelain::line(&[&summary.id, content_path.as_str()]));
+        }
+    }
+
+    if !args.porcelain {
+        eprintln!(
+            "Backed up {} paste(s) to `{}'.",
+            pastes.len(),
+            args.directory
+        );
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Context, anyhow};
+use clap::Args as ClapArgs;
+
+use crate::archive;
+use crate::config::Config;
+use crate::pager;
+use patisserie::api::{self, PasteryClient, extract_id};
+
+/// Fetch and concatenate several pastes to standard output.
+///
+/// Falls back to the local archive, if one was kept, for pastes that have
+/// already expired on pastery.net.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URLs or ids of the pastes to fetch, in order.
+    references: Vec<String>,
+
+    /// A string to print between each paste's content.
+    #[arg(long)]
+    separator: Option<String>,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let mut output = String::new();
+
+    for (i, reference) in args.references.iter().enumerate() {
+        if i > 0
+            && let Some(separator) = &args.separator
+        {
+            output.push_str(separator);
+        }
+
+        let id = extract_id(reference);
+        let body = match client.get(id) {
+            Ok(paste) => paste.body,
+            Err(api::Error::NotFound) => archive::retrieve(id)
+                .with_context(|| format!("Could not fetch paste `{}'", reference))?
+                .ok_or_else(|| anyhow!("Could not fetch paste `{}'", reference))?,
+            Err(err) => {
+                return Err(err).with_context(|| format!("Could not fetch paste `{}'", reference));
+            }
+        };
+
+        output.push_str(&body);
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    pager::page(&output)
+}
@@ -0,0 +1,97 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::language::parse_language;
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient, extract_id};
+
+/// Copy an existing paste's content into a new paste with different metadata.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the paste to clone.
+    reference: String,
+
+    /// The language for the new paste. Defaults to the original paste's language.
+    #[arg(short, long = "lang", value_parser = parse_language)]
+    language: Option<&'static str>,
+
+    /// The title for the new paste. Defaults to the original paste's title.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// The duration that the new paste will live for. Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The number of times the new paste can be viewed before expiring.
+    #[arg(long)]
+    max_views: Option<u32>,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let id = extract_id(&args.reference);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let original = client
+        .get(id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.reference))?;
+
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let language = args.language.unwrap_or(&original.language);
+
+    let cloned = client.create(
+        original.body,
+        NewPaste {
+            duration,
+            language,
+            title: args.title.or(original.title),
+            max_views: args.max_views,
+        },
+    )?;
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&cloned.url);
+        println!("{}", porcelain::line(&[&cloned.id, &cloned.url, &raw_url]));
+    } else {
+        println!("{}", cloned.url);
+    }
+
+    Ok(())
+}
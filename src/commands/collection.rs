@@ -0,0 +1,96 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use clap::{Args as ClapArgs, Subcommand};
+
+use crate::collection;
+use crate::config::Config;
+use crate::history;
+use crate::pager;
+use crate::porcelain;
+
+/// Manage local collections: named groups of related pastes.
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register a new, empty collection, so pastes can be added to it with
+    /// `paste --collection`.
+    Create {
+        /// The name of the collection, e.g. `incident-423`.
+        name: String,
+    },
+
+    /// List the pastes belonging to a collection, most recent first.
+    Show {
+        /// The name of the collection.
+        name: String,
+
+        /// Print a stable, tab-separated line (id, url, note) per paste
+        /// instead of human-facing output.
+        #[arg(long)]
+        porcelain: bool,
+    },
+}
+
+pub fn run(args: Args, _config: Config, _api_key: String) -> Result<(), anyhow::Error> {
+    match args.command {
+        Command::Create { name } => {
+            collection::create(&name)?;
+            println!("Created collection `{}'.", name);
+            Ok(())
+        }
+        Command::Show { name, porcelain } => show(&name, porcelain),
+    }
+}
+
+fn show(name: &str, porcelain: bool) -> Result<(), anyhow::Error> {
+    let entries: Vec<_> = history::list()?
+        .into_iter()
+        .filter(|entry| entry.tags.iter().any(|tag| tag == name))
+        .collect();
+
+    let mut output = String::new();
+
+    for entry in entries.iter().rev() {
+        let note = entry.note.as_deref().unwrap_or("");
+
+        if porcelain {
+            println!("{}", porcelain::line(&[&entry.id, &entry.url, note]));
+        } else {
+            output.push_str(&entry.url);
+            if !note.is_empty() {
+                output.push_str(&format!("  — {}", note));
+            }
+            output.push('\n');
+        }
+    }
+
+    if porcelain {
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        output.push_str(&format!("No pastes in collection `{}'.\n", name));
+    }
+
+    pager::page(&output)
+}
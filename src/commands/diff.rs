@@ -0,0 +1,112 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+use similar::TextDiff;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient, extract_id};
+
+/// Show a unified diff between two pastes.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the first paste.
+    first: String,
+
+    /// The URL or id of the second paste.
+    second: String,
+
+    /// Upload the diff as a new paste instead of printing it locally.
+    #[arg(long)]
+    paste: bool,
+
+    /// The duration that the diff paste will live for, with `--paste`.
+    /// Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The title for the diff paste, with `--paste`.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output, with `--paste`.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let first_id = extract_id(&args.first);
+    let second_id = extract_id(&args.second);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let first = client
+        .get(first_id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.first))?;
+    let second = client
+        .get(second_id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.second))?;
+
+    let diff = TextDiff::from_lines(&first.body, &second.body)
+        .unified_diff()
+        .header(&args.first, &args.second)
+        .to_string();
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if !args.paste {
+        print!("{}", diff);
+        return Ok(());
+    }
+
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let diff_paste = client.create(
+        diff,
+        NewPaste {
+            duration,
+            language: "diff",
+            title: args.title,
+            max_views: None,
+        },
+    )?;
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&diff_paste.url);
+        println!(
+            "{}",
+            porcelain::line(&[&diff_paste.id, &diff_paste.url, &raw_url])
+        );
+    } else {
+        println!("{}", diff_paste.url);
+    }
+
+    Ok(())
+}
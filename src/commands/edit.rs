@@ -0,0 +1,177 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+use std::process::Command;
+
+use anyhow::{Context, anyhow};
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+use time::OffsetDateTime;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::language::parse_language;
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient, extract_id};
+
+/// The editor command used when `--editor` is not given and `$EDITOR` is
+/// not set.
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+
+/// Download a paste, edit it locally, and upload the result as a new paste.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the paste to edit.
+    reference: String,
+
+    /// The editor command to use.
+    ///
+    /// Falls back to the `EDITOR` environment variable, then `vi` (or
+    /// `notepad` on Windows).
+    #[arg(long)]
+    editor: Option<String>,
+
+    /// The language for the new paste. Defaults to the original paste's language.
+    #[arg(short, long = "lang", value_parser = parse_language)]
+    language: Option<&'static str>,
+
+    /// The title for the new paste. Defaults to the original paste's title.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// The duration that the new paste will live for. Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// Delete the original paste once the edited one has been created.
+    #[arg(long)]
+    delete_original: bool,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+/// Writes `content` to a fresh temporary file, opens it in `editor` (split
+/// the same way `$PAGER` is in [`crate::highlight::page`], so an editor
+/// command with its own arguments like `code --wait` still works), and
+/// returns the file's contents once the editor exits successfully.
+fn edit(content: &str, editor: &str) -> Result<String, anyhow::Error> {
+    let path = std::env::temp_dir().join(format!(
+        "patisserie-edit-{}.txt",
+        OffsetDateTime::now_utc().unix_timestamp_nanos()
+    ));
+    let path = Utf8PathBuf::from_path_buf(path)
+        .map_err(|path| anyhow!("Temporary path `{}' is not valid UTF-8", path.display()))?;
+
+    fs::write(&path, content)
+        .with_context(|| format!("Could not write temporary file `{}'", path))?;
+
+    let result = run_editor(editor, &path);
+
+    let _ = fs::remove_file(&path);
+    result
+}
+
+fn run_editor(editor: &str, path: &Utf8PathBuf) -> Result<String, anyhow::Error> {
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow!("`--editor` value is empty"))?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path.as_str())
+        .status()
+        .with_context(|| format!("Could not run editor `{}'", editor))?;
+
+    if !status.success() {
+        return Err(anyhow!(
+            "editor `{}' exited with {}; aborting",
+            editor,
+            status
+        ));
+    }
+
+    fs::read_to_string(path).with_context(|| format!("Could not read temporary file `{}'", path))
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let id = extract_id(&args.reference);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let original = client
+        .get(id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.reference))?;
+
+    let editor = args
+        .editor
+        .clone()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_owned());
+
+    let edited = edit(&original.body, &editor)?;
+
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let language = args.language.unwrap_or(&original.language);
+
+    let edited_paste = client.create(
+        edited,
+        NewPaste {
+            duration,
+            language,
+            title: args.title.or(original.title),
+            max_views: None,
+        },
+    )?;
+
+    if args.delete_original {
+        client
+            .delete(id)
+            .with_context(|| format!("Could not delete original paste `{}'", args.reference))?;
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&edited_paste.url);
+        println!(
+            "{}",
+            porcelain::line(&[&edited_paste.id, &edited_paste.url, &raw_url])
+        );
+    } else {
+        println!("{}", edited_paste.url);
+    }
+
+    Ok(())
+}
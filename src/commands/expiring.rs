@@ -0,0 +1,83 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+use time::Duration;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::config::Config;
+use crate::duration;
+use crate::history;
+use crate::porcelain;
+
+/// List locally-tracked pastes that will expire soon.
+///
+/// Only considers pastes uploaded from this machine with `patisserie`
+/// (pastery.net's own listing API does not report a paste's expiry), which
+/// makes this best suited to running from cron to nudge yourself to renew
+/// the ones that matter before they're gone.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Only list pastes expiring within this duration, e.g. `2h` or `1d`.
+    #[arg(long)]
+    within: String,
+
+    /// Print a stable, tab-separated line (id, url, expires_at) per paste
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(args: Args, config: Config, _api_key: String) -> Result<(), anyhow::Error> {
+    let within_minutes = duration::resolve_duration(&args.within, &config.duration_aliases)?;
+    let now = OffsetDateTime::now_utc();
+    let threshold = now + Duration::minutes(i64::from(within_minutes));
+
+    let mut expiring = history::list()?
+        .into_iter()
+        .filter_map(|entry| {
+            let expires_at = OffsetDateTime::parse(entry.expires_at.as_deref()?, &Rfc3339).ok()?;
+            (expires_at > now && expires_at <= threshold).then_some((entry, expires_at))
+        })
+        .collect::<Vec<_>>();
+
+    expiring.sort_by_key(|(_, expires_at)| *expires_at);
+
+    for (entry, expires_at) in &expiring {
+        let expires_at = expires_at
+            .format(&Rfc3339)
+            .context("Could not format expiry timestamp")?;
+
+        if args.porcelain {
+            println!("{}", porcelain::line(&[&entry.id, &entry.url, &expires_at]));
+        } else {
+            print!("{}", entry.url);
+            if let Some(title) = &entry.title {
+                print!("  {}", title);
+            }
+            println!("  expires {}", expires_at);
+        }
+    }
+
+    if !args.porcelain && expiring.is_empty() {
+        println!("No pastes expiring within {}.", args.within);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::history::{self, HistoryEntry};
+use crate::porcelain;
+use crate::queue;
+use patisserie::api::{self, CreatedPaste, PasteryClient};
+
+/// Upload every paste spooled to the local queue, aliased as `retry` since
+/// pastes end up there either from `paste --offline-queue` while the network
+/// is down or after a recoverable upload failure.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Print a stable, tab-separated line (id, url, raw_url) per paste
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let queued_paths = queue::list().context("Could not read the offline queue")?;
+
+    if queued_paths.is_empty() {
+        if !args.porcelain {
+            println!("No queued pastes.");
+        }
+        return Ok(());
+    }
+
+    // Cache the paste list across retries, and only fetch it lazily: most
+    // retries won't need it, since most queued pastes have no history entry.
+    let mut remote_pastes = None;
+
+    for path in queued_paths {
+        let queued = queue::read(&path)?;
+        let hash = history::content_hash(&queued.body);
+
+        let already_uploaded = match history::find_by_hash(&hash)? {
+            Some(entry) => {
+                let remote_pastes = match &remote_pastes {
+                    Some(pastes) => pastes,
+                    None => remote_pastes.insert(client.list().context("Could not list pastes")?),
+                };
+
+                remote_pastes
+                    .iter()
+                    .any(|summary| summary.id == entry.id)
+                    .then_some(entry)
+            }
+            None => None,
+        };
+
+        let paste = match already_uploaded {
+            Some(entry) => CreatedPaste {
+                id: entry.id,
+                url: entry.url,
+            },
+            None => {
+                let paste = client
+                    .create(queued.body.clone(), queued.as_new_paste())
+                    .with_context(|| format!("Could not upload queued paste `{}'", path))?;
+
+                history::record(&HistoryEntry {
+                    hash,
+                    id: paste.id.clone(),
+                    url: paste.url.clone(),
+                    tags: queued.tags.clone(),
+                    note: None,
+                    title: queued.title.clone(),
+                    expires_at: Some(history::expiry_timestamp(queued.duration)),
+                })?;
+
+                paste
+            }
+        };
+
+        let raw_url = api::raw_url(&paste.url);
+        if args.porcelain {
+            println!("{}", porcelain::line(&[&paste.id, &paste.url, &raw_url]));
+        } else {
+            println!("{}", paste.url);
+        }
+
+        fs::remove_file(&path).with_context(|| format!("Could not remove file `{}'", path))?;
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Context, anyhow};
+use camino::Utf8Path;
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::language::{guess_language, parse_language};
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient};
+
+/// The maximum number of bytes to fetch from a URL, so a giant or
+/// accidentally-streaming response cannot exhaust memory.
+const DEFAULT_MAX_FETCH_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Fetch remote text content and paste it, without needing to download it
+/// with a separate tool first.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL to fetch and paste.
+    url: String,
+
+    /// The language for the paste.
+    ///
+    /// If not provided, patisserie will attempt to guess based on the
+    /// URL's path.
+    #[arg(short, long = "lang", value_parser = parse_language)]
+    language: Option<&'static str>,
+
+    /// The title of the paste.
+    ///
+    /// If not provided, the last path segment of the URL will be used
+    /// instead.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// The duration that this paste will live for. Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The number of times the paste can be viewed before expiring.
+    #[arg(long)]
+    max_views: Option<u32>,
+
+    /// The maximum number of bytes to fetch from the URL.
+    #[arg(long, default_value_t = DEFAULT_MAX_FETCH_SIZE)]
+    max_size: u64,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+/// Fetches `url`, refusing to buffer more than `max_size` bytes so that an
+/// accidentally huge or streaming response cannot exhaust memory.
+fn fetch(url: &str, max_size: u64) -> Result<String, anyhow::Error> {
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .send()
+        .with_context(|| format!("Could not fetch `{}'", url))?
+        .error_for_status()
+        .with_context(|| format!("`{}' returned an error", url))?;
+
+    let bytes = response
+        .bytes()
+        .with_context(|| format!("Could not read response body from `{}'", url))?;
+
+    if bytes.len() as u64 > max_size {
+        return Err(anyhow!(
+            "response from `{}' exceeds the {} byte limit; use --max-size to raise it",
+            url,
+            max_size
+        ));
+    }
+
+    String::from_utf8(bytes.to_vec())
+        .with_context(|| format!("Response from `{}' is not valid UTF-8", url))
+}
+
+/// The last non-empty path segment of `url`, used to guess a language and
+/// default title the same way a locally-uploaded file's name would be.
+fn last_path_segment(url: &str) -> Option<&str> {
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty() && !segment.contains(['?', '#']))
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let body = fetch(&args.url, args.max_size)?;
+
+    let filename = last_path_segment(&args.url);
+
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let language = args
+        .language
+        .or_else(|| filename.and_then(|name| guess_language(Utf8Path::new(name))))
+        .unwrap_or("autodetect");
+
+    let title = args
+        .title
+        .clone()
+        .or_else(|| filename.map(ToOwned::to_owned));
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let paste = client.create(
+        body,
+        NewPaste {
+            duration,
+            language,
+            title,
+            max_views: args.max_views,
+        },
+    )?;
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&paste.url);
+        println!("{}", porcelain::line(&[&paste.id, &paste.url, &raw_url]));
+    } else {
+        println!("{}", paste.url);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use anyhow::{Context, anyhow};
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+
+use crate::audit;
+use crate::config::Config;
+use crate::highlight;
+use crate::history;
+use crate::pager;
+use patisserie::api::{PasteryClient, extract_id};
+
+/// Fetch a paste's content.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the paste to fetch, or a shorthand history
+    /// reference: `last`, `~N`, or a bare `N` for the Nth most recent
+    /// upload.
+    reference: String,
+
+    /// Write the paste's content to a file instead of standard output.
+    ///
+    /// Pass `.` to write to a file in the current directory named after the
+    /// paste's title, rather than the content of the paste.
+    #[arg(short, long, conflicts_with = "highlight")]
+    output: Option<Utf8PathBuf>,
+
+    /// Overwrite the output file if it already exists.
+    #[arg(long)]
+    force: bool,
+
+    /// Render the paste with syntax highlighting and page through $PAGER,
+    /// like a remote `bat`.
+    #[arg(long)]
+    highlight: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let resolved = history::resolve_id(&args.reference)?;
+    let id = extract_id(&resolved);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let paste = client
+        .get(id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.reference))?;
+    crate::commands::report_rate_limit(&client, verbose);
+
+    audit::record(
+        &config,
+        "get",
+        &resolved,
+        Some(paste.body.len()),
+        Some(&history::content_hash(&paste.body)),
+    )?;
+
+    if args.highlight {
+        let rendered = highlight::highlight(&paste.body, &paste.language);
+        return pager::page(&rendered);
+    }
+
+    let Some(output) = &args.output else {
+        return pager::page(&paste.body);
+    };
+
+    let path = if output.as_str() == "." {
+        Utf8PathBuf::from(paste.title.as_deref().ok_or_else(|| {
+            anyhow!(
+                "Paste `{}' has no title to restore a filename from",
+                args.reference
+            )
+        })?)
+    } else {
+        output.clone()
+    };
+
+    let mut file = OpenOptions::new();
+    file.write(true);
+    if args.force {
+        file.create(true).truncate(true);
+    } else {
+        file.create_new(true);
+    }
+
+    file.open(&path)
+        .with_context(|| format!("Could not open file `{}' for writing", path))?
+        .write_all(paste.body.as_bytes())
+        .with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use clap::{Args as ClapArgs, Subcommand};
+
+use crate::config::Config;
+use crate::history;
+use crate::pager;
+use crate::porcelain;
+
+/// List pastes previously uploaded from this machine, most recent first.
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Only show pastes tagged with this value, e.g. `--tag incident-423`.
+    ///
+    /// Can be given more than once, in which case only pastes tagged with
+    /// every value given are shown.
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+
+    /// Print a stable, tab-separated line (id, url, tags, note) per paste
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Attach a free-form note to a previously uploaded paste.
+    Annotate {
+        /// The id of the paste, as recorded in history.
+        id: String,
+
+        /// The note to attach, replacing any note already there.
+        note: String,
+    },
+}
+
+pub fn run(args: Args, _config: Config, _api_key: String) -> Result<(), anyhow::Error> {
+    match args.command {
+        Some(Command::Annotate { id, note }) => history::annotate(&id, &note),
+        None => list(&args.tags, args.porcelain),
+    }
+}
+
+fn list(tags: &[String], porcelain: bool) -> Result<(), anyhow::Error> {
+    let entries: Vec<_> = history::list()?
+        .into_iter()
+        .filter(|entry| tags.iter().all(|tag| entry.tags.contains(tag)))
+        .collect();
+
+    let mut output = String::new();
+
+    for entry in entries.iter().rev() {
+        let note = entry.note.as_deref().unwrap_or("");
+
+        if porcelain {
+            println!(
+                "{}",
+                porcelain::line(&[&entry.id, &entry.url, &entry.tags.join(","), note])
+            );
+        } else {
+            output.push_str(&entry.url);
+            if !entry.tags.is_empty() {
+                output.push_str(&format!("  [{}]", entry.tags.join(", ")));
+            }
+            if !note.is_empty() {
+                output.push_str(&format!("  — {}", note));
+            }
+            output.push('\n');
+        }
+    }
+
+    if porcelain {
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        output.push_str("No matching pastes in history.\n");
+    }
+
+    pager::page(&output)
+}
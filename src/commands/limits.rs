@@ -0,0 +1,66 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::porcelain;
+use patisserie::api::PasteryClient;
+
+/// Show the current rate-limit quota, so heavy scripted users can pace
+/// themselves.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Print a stable, tab-separated line (limit, remaining, reset_secs)
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(args: Args, config: Config, api_key: String) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+    client.list().context("Could not check the rate limit")?;
+
+    let rate_limit = client.last_rate_limit();
+
+    if args.porcelain {
+        let limit = rate_limit.and_then(|rl| rl.limit).map(|v| v.to_string());
+        let remaining = rate_limit
+            .and_then(|rl| rl.remaining)
+            .map(|v| v.to_string());
+        let reset_secs = rate_limit
+            .and_then(|rl| rl.reset_secs)
+            .map(|v| v.to_string());
+
+        println!(
+            "{}",
+            porcelain::line(&[
+                limit.as_deref().unwrap_or(""),
+                remaining.as_deref().unwrap_or(""),
+                reset_secs.as_deref().unwrap_or(""),
+            ])
+        );
+    } else {
+        match rate_limit {
+            Some(rate_limit) => println!("{}", rate_limit),
+            None => println!("Pastery.net did not report a rate-limit quota."),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+pub mod backup;
+pub mod cat;
+pub mod clone;
+pub mod collection;
+pub mod copy_url;
+pub mod diff;
+pub mod edit;
+pub mod expiring;
+pub mod flush;
+pub mod from_url;
+pub mod get;
+pub mod history;
+pub mod limits;
+pub mod open;
+pub mod paste;
+pub mod pick;
+pub mod ping;
+pub mod purge;
+pub mod renew;
+pub mod restore;
+pub mod search;
+pub mod series;
+pub mod share;
+pub mod template;
+pub mod verify;
+pub mod version;
+
+use patisserie::api::PasteryClient;
+
+/// The names of all subcommands, used to decide whether `paste` should be
+/// inserted implicitly when none is given on the command line.
+pub const NAMES: &[&str] = &[
+    "paste",
+    "purge",
+    "renew",
+    "clone",
+    "verify",
+    "get",
+    "cat",
+    "backup",
+    "restore",
+    "limits",
+    "flush",
+    "retry",
+    "history",
+    "share",
+    "expiring",
+    "ping",
+    "version",
+    "from-url",
+    "edit",
+    "diff",
+    "series",
+    "template",
+    "collection",
+    "search",
+    "pick",
+    "open",
+    "copy-url",
+];
+
+/// If `verbose`, prints the rate-limit quota observed in `client`'s most
+/// recent response to standard error, so heavy scripted users can pace
+/// themselves without running a separate `limits` call.
+pub fn report_rate_limit(client: &PasteryClient, verbose: bool) {
+    if verbose && let Some(rate_limit) = client.last_rate_limit() {
+        crate::log::info(&format!("Rate limit: {}", rate_limit));
+    }
+}
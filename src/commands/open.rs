@@ -0,0 +1,49 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::anyhow;
+use clap::Args as ClapArgs;
+
+use crate::browser;
+use crate::config::Config;
+use crate::history;
+
+/// Open a previously uploaded paste in a browser, looked up from local
+/// history.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Which paste to open: `last` for the most recent, `3` or `~3` for
+    /// the 3rd most recent, or the id of a specific paste.
+    reference: String,
+
+    /// The browser command to use, e.g. `firefox`.
+    ///
+    /// Falls back to the `browser` set in the config file, then the
+    /// `BROWSER` environment variable, then the system default browser.
+    #[arg(long)]
+    browser: Option<String>,
+}
+
+pub fn run(args: Args, config: Config) -> Result<(), anyhow::Error> {
+    let entry = history::resolve(&args.reference)?
+        .ok_or_else(|| anyhow!("No history entry found for `{}'", args.reference))?;
+
+    let browser = args.browser.as_deref().or(config.browser.as_deref());
+    browser::open(&entry.url, browser);
+
+    Ok(())
+}
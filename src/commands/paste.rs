@@ -0,0 +1,1649 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, IsTerminal, Read, Write, stderr, stdin, stdout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Args as ClapArgs;
+use gethostname::gethostname;
+use time::macros::format_description;
+use time::{Duration, OffsetDateTime};
+
+use anyhow::{Context, anyhow};
+
+use crate::anonymize;
+use crate::ansi;
+use crate::archive;
+use crate::audit;
+use crate::browser;
+use crate::clipboard;
+use crate::collection;
+use crate::color;
+use crate::config::{Config, FiletypeConfig};
+use crate::desktop;
+use crate::duration::{self, ONE_DAY, ONE_MINUTE};
+use crate::email;
+use crate::filter;
+use crate::highlight;
+use crate::history::{self, HistoryEntry};
+use crate::hooks;
+use crate::i18n;
+use crate::language::{guess_language, parse_language};
+use crate::matrix;
+use crate::porcelain;
+use crate::queue::{self, QueuedPaste};
+use crate::shell;
+use crate::slack;
+use crate::spinner::Spinner;
+use crate::throttle::Throttle;
+use crate::timings;
+use crate::webhook;
+use patisserie::api::{self, CreatedPaste, NewPaste, PasteryClient};
+
+const DEFAULT_DURATION: u32 = ONE_DAY;
+const ONCE_DURATION: u32 = 10 * ONE_MINUTE;
+const DEFAULT_MAX_STDIN_SIZE: u64 = 100 * 1024 * 1024;
+const TITLE_FROM_CONTENT_MAX_LEN: usize = 80;
+const GLOB_CONFIRM_THRESHOLD: usize = 20;
+const DIRECTORY_CONFIRM_THRESHOLD: usize = 20;
+
+/// Shared across every upload in a batch, keyed by content hash, so that a
+/// batch of files with identical content after filtering only hits the
+/// network once.
+///
+/// Each hash maps to a per-hash mutex around the eventual [`CreatedPaste`],
+/// reserved (as `None`) before the upload starts rather than after it
+/// finishes, so two workers racing on identical content under `--jobs`
+/// serialize on the same upload instead of both missing the cache and both
+/// hitting the network.
+type Dedup = Mutex<HashMap<String, Arc<Mutex<Option<CreatedPaste>>>>>;
+
+/// Upload a new paste (the default command).
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The duration that this paste will live for.
+    ///
+    /// After this time, the paste will be deleted.
+    ///
+    /// You can specify a period of minutes or a value followed by one of the following units:
+    /// m(inute), h(our), d(ay), mo(nth), y(ear). You can also pass `max` or `forever` for the
+    /// longest duration the service allows, or the name of a duration alias defined in the
+    /// config file's `[duration_aliases]` section.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The language for the paste.
+    ///
+    /// If not provided, patisserie will attempt to guess based on the file
+    /// extension. You can use the special value "autodetect" to have pastery
+    /// detect the language.
+    #[arg(short, long = "lang", value_parser = parse_language)]
+    language: Option<&'static str>,
+
+    /// The title of the paste.
+    ///
+    /// If not provided, the name of the file will be used instead.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// Derive the title from the first non-empty line of the content.
+    ///
+    /// This is most useful when pasting from standard input, where there is
+    /// no filename to fall back on. It takes precedence over the filename
+    /// when both are available.
+    #[arg(long)]
+    title_from_content: bool,
+
+    /// The number of times the paste can be viewed before expiring.
+    ///
+    /// If not provided, this falls back to the `max_views` set in the config
+    /// file, if any. Pass `--max-views 0` to explicitly disable view-based
+    /// expiration even when the config file sets a default.
+    #[arg(long, conflicts_with = "once")]
+    max_views: Option<u32>,
+
+    /// Shortcut for sharing something that should only be seen once.
+    ///
+    /// Equivalent to `--max-views 1` with a short duration, unless `--duration`
+    /// is given explicitly.
+    #[arg(long)]
+    once: bool,
+
+    /// Fetch the paste back after uploading and compare it byte-for-byte
+    /// with what was sent, reporting any mismatch.
+    #[arg(long)]
+    verify: bool,
+
+    /// Print a DNS/connect/TLS/transfer timing breakdown and payload size
+    /// to standard error after uploading, for diagnosing why pasting is
+    /// slow from a particular host.
+    #[arg(long)]
+    timings: bool,
+
+    /// Render the content with syntax highlighting and show it before
+    /// uploading, asking for confirmation to proceed.
+    ///
+    /// Requires a controlling terminal to confirm on; the upload is
+    /// aborted if there isn't one.
+    #[arg(long)]
+    preview_highlight: bool,
+
+    /// Render Markdown content locally and show it before uploading, asking
+    /// for confirmation to proceed.
+    ///
+    /// Only applies when the detected language is Markdown; ignored
+    /// otherwise. Requires a controlling terminal to confirm on; the upload
+    /// is aborted if there isn't one.
+    #[arg(long, conflicts_with = "preview_highlight")]
+    preview_render: bool,
+
+    /// Print the direct raw-text URL instead of the paste's HTML page URL.
+    #[arg(long, conflicts_with = "id_only")]
+    raw_url: bool,
+
+    /// Print the result as a JSON object with both the page and raw URLs.
+    #[arg(long, conflicts_with = "id_only")]
+    json: bool,
+
+    /// Print just the paste's id, for downstream tooling that does not need
+    /// the full URL.
+    #[arg(long)]
+    id_only: bool,
+
+    /// Print a stable, tab-separated line (id, url, raw_url, expires)
+    /// instead of human-facing output.
+    #[arg(long, conflicts_with_all = ["json", "id_only"])]
+    porcelain: bool,
+
+    /// Format the printed link for a chat medium, so it pastes cleanly
+    /// with correct link syntax.
+    #[arg(long, value_parser = parse_format_preset, conflicts_with_all = ["json", "porcelain", "id_only"])]
+    format_preset: Option<FormatPreset>,
+
+    /// The maximum number of upload requests to make per minute when
+    /// uploading more than one path.
+    ///
+    /// If not provided, this falls back to the `requests_per_minute` set in
+    /// the config file, if any. Otherwise, batch uploads are not throttled.
+    #[arg(long)]
+    requests_per_minute: Option<u32>,
+
+    /// If pastery.net cannot be reached, spool the paste to a local queue
+    /// instead of failing, without asking for confirmation. Run
+    /// `patisserie flush` later to upload it.
+    ///
+    /// Without this flag, a hard failure after retries still offers to
+    /// spool the paste on the controlling terminal, so a carefully
+    /// assembled `--lines`/filtered payload is never silently lost.
+    #[arg(long)]
+    offline_queue: bool,
+
+    /// Keep a local archive of the paste's content, so it outlives the
+    /// paste's expiry on pastery.net.
+    ///
+    /// If not passed, this falls back to the `archive_uploads` setting in
+    /// the config file.
+    #[arg(long)]
+    archive: bool,
+
+    /// The number of uploads to run concurrently when more than one path is
+    /// given.
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// When uploading more than one file, also create an extra "index"
+    /// paste listing every file's name and URL, and print only that
+    /// paste's URL instead of each file's own.
+    #[arg(long)]
+    index: bool,
+
+    /// The maximum number of bytes to read from standard input.
+    ///
+    /// This only applies when reading from standard input; local files are
+    /// not subject to this limit.
+    #[arg(long, default_value_t = DEFAULT_MAX_STDIN_SIZE)]
+    max_size: u64,
+
+    /// The maximum time to wait for standard input to be read in full, e.g.
+    /// `30s`.
+    ///
+    /// If not provided, this falls back to the `stdin_timeout_secs` set in
+    /// the config file, if any. Only applies when reading from standard
+    /// input; there is no timeout by default.
+    #[arg(long, value_parser = parse_seconds)]
+    stdin_timeout: Option<u64>,
+
+    /// Pipe the content through an external shell pipeline before
+    /// uploading, e.g. `--filter 'grep -v password | tail -n 500'`.
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Strip ANSI escape codes (e.g. color codes) from the content before
+    /// uploading.
+    ///
+    /// If not passed, this falls back to the `strip_ansi` setting for the
+    /// file's extension in the config file's `[filetype.*]` section, if
+    /// any.
+    #[arg(long)]
+    strip_ansi: bool,
+
+    /// Rewrite `/home/<user>`, `C:\Users\<user>`, the current hostname, and
+    /// the current username throughout the content to placeholders before
+    /// uploading, so stack traces and logs don't leak local identifiers.
+    #[arg(long)]
+    anonymize: bool,
+
+    /// Truncate the content to at most this many lines before uploading.
+    ///
+    /// If not provided, this falls back to the `max_lines` setting for the
+    /// file's extension in the config file's `[filetype.*]` section, if
+    /// any.
+    #[arg(long)]
+    max_lines: Option<usize>,
+
+    /// Validate the content as JSON, YAML, or TOML (based on the detected
+    /// language) and warn about any syntax error before uploading, without
+    /// aborting the upload.
+    ///
+    /// Catches the classic "pasted a truncated JSON" problem.
+    #[arg(long)]
+    check: bool,
+
+    /// Strip insignificant whitespace from JSON or XML content (based on the
+    /// detected language) before uploading, to keep the paste as small as
+    /// possible.
+    ///
+    /// Left unchanged if the content is not valid JSON, or is not detected
+    /// as JSON or XML at all.
+    #[arg(long)]
+    minify: bool,
+
+    /// Soft-wrap lines longer than this many characters before uploading.
+    ///
+    /// Useful for minified JavaScript or single-line JSON, which would
+    /// otherwise require horizontal scrolling to read in a browser.
+    #[arg(long)]
+    wrap: Option<usize>,
+
+    /// Prefix each line with its (aligned) line number before uploading.
+    ///
+    /// Useful when the recipient needs to reference a specific line and
+    /// pastery's own viewer numbering is disabled or unavailable, such as in
+    /// raw view.
+    #[arg(long)]
+    line_numbers: bool,
+
+    /// Echo the content locally while uploading it, so a paste piped from
+    /// another command is still visible.
+    ///
+    /// With no argument, or `-`, the content is written to standard output
+    /// and upload results move to standard error instead, so that stdout
+    /// carries exactly the piped content, as with the `tee` command. With a
+    /// path, the content is written to that file instead, and results are
+    /// printed as usual.
+    #[arg(long, num_args = 0..=1, default_missing_value = "-")]
+    tee: Option<Utf8PathBuf>,
+
+    /// Prepend a short provenance header (timestamp, host, command, and
+    /// patisserie version) to the content before uploading.
+    ///
+    /// Off by default.
+    #[arg(long)]
+    stamp: bool,
+
+    /// Tag this paste for later lookup with `patisserie history --tag`.
+    ///
+    /// Can be given more than once, e.g. `--tag incident-423 --tag db`.
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+
+    /// Add this paste to a local collection, so it can be found later with
+    /// `patisserie collection show` and managed as a group.
+    ///
+    /// The collection must already exist; create it first with `patisserie
+    /// collection create NAME`. Implemented as a tag, so it also shows up
+    /// under `patisserie history --tag`.
+    #[arg(long)]
+    collection: Option<String>,
+
+    /// POST a JSON payload (url, title, language, expiry) to this URL after
+    /// a successful upload, so team dashboards or bots can track shared
+    /// pastes.
+    ///
+    /// If not provided, this falls back to the `webhook_url` set in the
+    /// config file, if any.
+    #[arg(long)]
+    webhook: Option<String>,
+
+    /// Post the paste's link and title to a chat channel immediately after
+    /// upload, e.g. `--notify slack:#general` or `--notify
+    /// matrix:!room:server`.
+    ///
+    /// Slack uses the `slack_webhook_url` set in the config file; Matrix
+    /// uses `matrix_homeserver_url` and `matrix_access_token`.
+    #[arg(long, value_parser = parse_notify_target)]
+    notify: Option<NotifyTarget>,
+
+    /// Show a desktop notification with the paste's URL when the upload
+    /// finishes or fails, so an upload left running in the background
+    /// still surfaces its result.
+    #[arg(long)]
+    desktop_notify: bool,
+
+    /// Email the paste's link and title to this address after a successful
+    /// upload.
+    ///
+    /// Uses the `smtp_server` set in the config file, if any, or the local
+    /// `sendmail` binary otherwise.
+    #[arg(long)]
+    email: Option<String>,
+
+    /// Copy the paste's URL to the clipboard after a successful upload.
+    ///
+    /// The mechanism is detected automatically (the Windows clipboard under
+    /// WSL; tmux, Wayland, or X11 on Linux; the platform clipboard on macOS
+    /// and Windows; OSC 52 otherwise), or can be pinned with the
+    /// `clipboard_backend` config setting.
+    #[arg(long)]
+    copy: bool,
+
+    /// Load the paste's URL into the tmux paste buffer with `tmux
+    /// set-buffer`, so it can be pasted inside the same tmux session
+    /// without touching the system clipboard.
+    #[arg(long)]
+    tmux: bool,
+
+    /// Open the paste's URL in a browser after a successful upload.
+    #[arg(long)]
+    open: bool,
+
+    /// The browser command used by `--open`, e.g. `firefox`.
+    ///
+    /// Falls back to the `browser` set in the config file, then the
+    /// `BROWSER` environment variable, then the system default browser.
+    #[arg(long)]
+    browser: Option<String>,
+
+    /// The paths of the files to upload.
+    ///
+    /// If not provided, the file will be read from standard input. Given
+    /// more than one path, each is uploaded as its own paste and results are
+    /// printed in the order the paths were given, unless `--index` is set. A
+    /// path containing `*`, `?`, or `[` is expanded as a glob pattern, which
+    /// is useful on Windows, where the shell does not expand these itself. A
+    /// directory is walked recursively, honouring `.gitignore`, and a
+    /// path-to-URL mapping is printed afterwards.
+    paths: Vec<Utf8PathBuf>,
+}
+
+#[cfg(unix)]
+const TTY_PATH: &str = "/dev/tty";
+#[cfg(windows)]
+const TTY_PATH: &str = "CON";
+
+/// Prompts the user for a title on the controlling terminal.
+///
+/// This is used when content is being read directly from an interactive
+/// terminal, since standard input has already been consumed by the time we
+/// know a title is needed.
+fn prompt_for_title() -> Option<String> {
+    eprint!("Title: ");
+    stderr().flush().ok()?;
+
+    let tty = File::open(TTY_PATH).ok()?;
+    let mut line = String::new();
+    BufReader::new(tty).read_line(&mut line).ok()?;
+
+    let title = line.trim();
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_owned())
+    }
+}
+
+/// Expands any path in `paths` containing `*`, `?`, or `[` into the files it
+/// matches on disk, so globs work even under shells (like Windows') that do
+/// not expand them.
+///
+/// Asks for confirmation on the controlling terminal before uploading more
+/// than [`GLOB_CONFIRM_THRESHOLD`] glob-matched files, so a typo in a
+/// pattern cannot silently kick off a huge batch of uploads.
+fn expand_globs(paths: Vec<Utf8PathBuf>) -> Result<Vec<Utf8PathBuf>, anyhow::Error> {
+    let mut expanded = Vec::with_capacity(paths.len());
+    let mut glob_matched = 0;
+
+    for path in paths {
+        if !path.as_str().contains(['*', '?', '[']) {
+            expanded.push(path);
+            continue;
+        }
+
+        let matches: Vec<Utf8PathBuf> = glob::glob(path.as_str())
+            .with_context(|| format!("Invalid glob pattern `{}'", path))?
+            .filter_map(Result::ok)
+            .filter_map(|path| Utf8PathBuf::from_path_buf(path).ok())
+            .collect();
+
+        if matches.is_empty() {
+            return Err(anyhow!("Pattern `{}' did not match any files", path));
+        }
+
+        glob_matched += matches.len();
+        expanded.extend(matches);
+    }
+
+    if glob_matched > GLOB_CONFIRM_THRESHOLD && !confirm_glob_expansion(glob_matched) {
+        return Err(anyhow!(
+            "aborted: {} files matched by glob patterns",
+            glob_matched
+        ));
+    }
+
+    Ok(expanded)
+}
+
+/// Asks on the controlling terminal whether to proceed with uploading
+/// `count` glob-matched files, defaulting to no if there is no controlling
+/// terminal to ask on.
+fn confirm_glob_expansion(count: usize) -> bool {
+    eprint!("This will upload {} files. Continue? [y/N] ", count);
+    if stderr().flush().is_err() {
+        return false;
+    }
+
+    let Ok(tty) = File::open(TTY_PATH) else {
+        return false;
+    };
+
+    let mut line = String::new();
+    if BufReader::new(tty).read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim(), "y" | "Y" | "yes" | "Yes")
+}
+
+/// Asks on the controlling terminal whether to spool a paste that failed to
+/// upload to the offline queue instead of losing it, defaulting to no
+/// confirmation if there is no controlling terminal to ask on.
+fn confirm_save_to_queue() -> bool {
+    eprint!("Could not upload this paste. Save it to the offline queue instead? [y/N] ");
+    if stderr().flush().is_err() {
+        return false;
+    }
+
+    let Ok(tty) = File::open(TTY_PATH) else {
+        return false;
+    };
+
+    let mut line = String::new();
+    if BufReader::new(tty).read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim(), "y" | "Y" | "yes" | "Yes")
+}
+
+/// Prints `rendered` and asks on the controlling terminal whether to
+/// proceed with the upload, defaulting to yes on the controlling terminal
+/// and refusing outright if there is none, for `--preview-highlight`.
+fn confirm_preview(rendered: &str) -> bool {
+    eprintln!("{}", rendered);
+    eprint!("Upload this? [Y/n] ");
+    if stderr().flush().is_err() {
+        return false;
+    }
+
+    let Ok(tty) = File::open(TTY_PATH) else {
+        return false;
+    };
+
+    let mut line = String::new();
+    if BufReader::new(tty).read_line(&mut line).is_err() {
+        return false;
+    }
+
+    let answer = line.trim();
+    answer.is_empty() || matches!(answer, "y" | "Y" | "yes" | "Yes")
+}
+
+/// Derives a title from the first non-empty line of `content`, truncating it
+/// if necessary.
+fn title_from_content(content: &str) -> Option<String> {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+
+    if line.chars().count() > TITLE_FROM_CONTENT_MAX_LEN {
+        let truncated: String = line.chars().take(TITLE_FROM_CONTENT_MAX_LEN).collect();
+        Some(format!("{}...", truncated))
+    } else {
+        Some(line.to_owned())
+    }
+}
+
+/// Looks up the `[filetype.*]` config section matching `path`'s extension,
+/// if any.
+fn filetype_config<'a>(config: &'a Config, path: Option<&Utf8Path>) -> Option<&'a FiletypeConfig> {
+    let extension = path.and_then(Utf8Path::extension)?;
+    config.filetypes.get(&extension.to_ascii_lowercase())
+}
+
+/// Truncates `content` to at most `max_lines` lines, appending a note about
+/// how many lines were cut if any were.
+fn truncate_lines(content: &str, max_lines: usize) -> String {
+    let total_lines = content.lines().count();
+    if total_lines <= max_lines {
+        return content.to_owned();
+    }
+
+    let mut truncated = content
+        .lines()
+        .take(max_lines)
+        .collect::<Vec<_>>()
+        .join("\n");
+    truncated.push_str(&format!(
+        "\n... truncated {} of {} lines ...\n",
+        total_lines - max_lines,
+        total_lines
+    ));
+    truncated
+}
+
+/// A structured format `--check` knows how to validate.
+#[derive(Clone, Copy)]
+enum CheckKind {
+    Json,
+    Yaml,
+    Toml,
+}
+
+/// Determines which format, if any, `--check` should validate `path`'s (or
+/// the pastery `language` tag's) content as.
+///
+/// The file extension is consulted first, since pastery's own language tags
+/// have no `yaml` or `toml` extension mapping of their own.
+fn detect_check_kind(path: Option<&Utf8Path>, language: &str) -> Option<CheckKind> {
+    match path
+        .and_then(Utf8Path::extension)
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("json") => return Some(CheckKind::Json),
+        Some("yaml") | Some("yml") => return Some(CheckKind::Yaml),
+        Some("toml") => return Some(CheckKind::Toml),
+        _ => {}
+    }
+
+    match language {
+        "json" | "jsonld" => Some(CheckKind::Json),
+        "yaml" | "yaml+jinja" => Some(CheckKind::Yaml),
+        _ => None,
+    }
+}
+
+/// Validates `content` as `kind`, returning the parse error's message if it
+/// is not well-formed.
+fn check_syntax(kind: CheckKind, content: &str) -> Result<(), String> {
+    match kind {
+        CheckKind::Json => serde_json::from_str::<serde_json::Value>(content)
+            .map(|_| ())
+            .map_err(|err| err.to_string()),
+        CheckKind::Yaml => serde_yaml::from_str::<serde_yaml::Value>(content)
+            .map(|_| ())
+            .map_err(|err| err.to_string()),
+        CheckKind::Toml => content
+            .parse::<toml::Value>()
+            .map(|_| ())
+            .map_err(|err| err.to_string()),
+    }
+}
+
+/// A structured format `--minify` knows how to strip whitespace from.
+#[derive(Clone, Copy)]
+enum MinifyKind {
+    Json,
+    Xml,
+}
+
+/// Determines which format, if any, `--minify` should treat `path`'s (or
+/// the pastery `language` tag's) content as.
+fn detect_minify_kind(path: Option<&Utf8Path>, language: &str) -> Option<MinifyKind> {
+    match path
+        .and_then(Utf8Path::extension)
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("json") => return Some(MinifyKind::Json),
+        Some("xml") => return Some(MinifyKind::Xml),
+        _ => {}
+    }
+
+    if language == "json" || language == "jsonld" {
+        Some(MinifyKind::Json)
+    } else if language == "xml" || language.starts_with("xml+") {
+        Some(MinifyKind::Xml)
+    } else {
+        None
+    }
+}
+
+/// Re-serializes `content` as compact JSON, preserving key order, or returns
+/// it unchanged if it is not valid JSON.
+fn minify_json(content: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return content.to_owned();
+    };
+
+    serde_json::to_string(&value).unwrap_or_else(|_| content.to_owned())
+}
+
+/// Strips whitespace-only text between tags from `content`, e.g. the
+/// indentation of a pretty-printed document, without a full XML parse.
+fn minify_xml(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut after_tag = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '>' => {
+                output.push(c);
+                after_tag = true;
+            }
+            '<' => {
+                output.push(c);
+                after_tag = false;
+            }
+            c if after_tag && c.is_whitespace() => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+/// Strips insignificant whitespace from `content` as `kind`, for
+/// `--minify`.
+fn minify(kind: MinifyKind, content: &str) -> String {
+    match kind {
+        MinifyKind::Json => minify_json(content),
+        MinifyKind::Xml => minify_xml(content),
+    }
+}
+
+/// Soft-wraps every line of `content` longer than `width` characters, so it
+/// stays readable in a browser without horizontal scrolling, for `--wrap`.
+fn wrap_lines(content: &str, width: usize) -> String {
+    content
+        .lines()
+        .flat_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                return vec![String::new()];
+            }
+            chars
+                .chunks(width)
+                .map(|chunk| chunk.iter().collect())
+                .collect::<Vec<String>>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prefixes each line of `content` with its 1-based line number, right-aligned
+/// to the width of the last line number, for `--line-numbers`.
+fn add_line_numbers(content: &str) -> String {
+    let width = content.lines().count().to_string().len();
+
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| format!("{:>width$}  {}", index + 1, line, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses a number of seconds, optionally suffixed with `s` (e.g. `30` or
+/// `30s`).
+fn parse_seconds(s: &str) -> Result<u64, anyhow::Error> {
+    s.strip_suffix('s')
+        .unwrap_or(s)
+        .parse()
+        .map_err(|_| anyhow!("Expected a number of seconds, got `{}'", s))
+}
+
+/// A chat channel to notify with the paste's link after a successful
+/// upload, as given to `--notify`.
+#[derive(Clone)]
+enum NotifyTarget {
+    Slack(String),
+    Matrix(String),
+}
+
+/// Parses a `--notify` value like `slack:#general` or `matrix:!room:server`.
+fn parse_notify_target(s: &str) -> Result<NotifyTarget, anyhow::Error> {
+    match s.split_once(':') {
+        Some(("slack", channel)) if !channel.is_empty() => {
+            Ok(NotifyTarget::Slack(channel.to_owned()))
+        }
+        Some(("matrix", room_id)) if !room_id.is_empty() => {
+            Ok(NotifyTarget::Matrix(room_id.to_owned()))
+        }
+        _ => Err(anyhow!(
+            "Expected a notification target like `slack:#channel' or `matrix:!room:server', got `{}'",
+            s
+        )),
+    }
+}
+
+/// A chat medium to format the printed paste link for, as given to
+/// `--format-preset`.
+#[derive(Clone, Copy)]
+enum FormatPreset {
+    Discord,
+    Slack,
+    Markdown,
+    Html,
+}
+
+/// Parses a `--format-preset` value.
+fn parse_format_preset(s: &str) -> Result<FormatPreset, anyhow::Error> {
+    match s {
+        "discord" => Ok(FormatPreset::Discord),
+        "slack" => Ok(FormatPreset::Slack),
+        "markdown" => Ok(FormatPreset::Markdown),
+        "html" => Ok(FormatPreset::Html),
+        _ => Err(anyhow!(
+            "Expected one of `discord', `slack', `markdown', or `html', got `{}'",
+            s
+        )),
+    }
+}
+
+/// Formats `url` (and `title`, if any) for `preset`, so the printed line
+/// pastes cleanly into that chat medium with correct link syntax.
+fn format_for_preset(preset: FormatPreset, title: Option<&str>, url: &str) -> String {
+    let title = title.unwrap_or(url);
+
+    match preset {
+        FormatPreset::Discord => format!("**{}**: <{}>", title, url),
+        FormatPreset::Slack => format!("<{}|{}>", url, title),
+        FormatPreset::Markdown => format!("[{}]({})", title, url),
+        FormatPreset::Html => format!("<a href=\"{}\">{}</a>", url, title),
+    }
+}
+
+/// Substitutes `{url}`, `{id}`, `{title}`, `{language}`, and `{expiry}`
+/// placeholders in `template`, for the `on_success` config setting.
+///
+/// Every substituted value is shell-quoted, since `title` (and, via
+/// `--title-from-content`, the paste body itself) may contain content the
+/// user does not fully control; without quoting, a crafted title could
+/// break out of its placeholder and inject additional shell commands.
+fn render_on_success_template(
+    template: &str,
+    paste: &CreatedPaste,
+    title: Option<&str>,
+    language: &str,
+    expiry: &str,
+) -> String {
+    template
+        .replace("{url}", &shell_words::quote(&paste.url))
+        .replace("{id}", &shell_words::quote(&paste.id))
+        .replace("{title}", &shell_words::quote(title.unwrap_or("")))
+        .replace("{language}", &shell_words::quote(language))
+        .replace("{expiry}", &shell_words::quote(expiry))
+}
+
+/// Writes `content` to `target`, or to standard output if `target` is `-`.
+fn write_tee(target: &Utf8Path, content: &str) -> Result<(), anyhow::Error> {
+    if target == "-" {
+        stdout()
+            .write_all(content.as_bytes())
+            .context("Could not write content to stdout")
+    } else {
+        std::fs::write(target, content)
+            .with_context(|| format!("Could not write content to `{}'", target))
+    }
+}
+
+/// Reads `path` into a `String` in a single allocation.
+fn read_file(path: &Utf8Path) -> Result<String, anyhow::Error> {
+    let bytes =
+        fs::read(path).with_context(|| format!("Could not open file `{}' for reading", path))?;
+
+    // `String::from_utf8` validates and takes ownership of `bytes` in
+    // place; it does not allocate a second buffer. Reading into an owned
+    // `String` directly like this, rather than memory-mapping and then
+    // copying out of the mapping, avoids ever holding two full copies of
+    // the file in RAM at once.
+    String::from_utf8(bytes).with_context(|| format!("File `{}' is not valid UTF-8", path))
+}
+
+/// Reads standard input into a `String`, refusing to buffer more than
+/// `max_size` bytes so that an accidentally attached giant pipe cannot
+/// exhaust memory.
+fn read_stdin(max_size: u64, timeout: Option<StdDuration>) -> Result<String, anyhow::Error> {
+    let bytes = match timeout {
+        None => {
+            let mut bytes = Vec::new();
+            stdin()
+                .take(max_size + 1)
+                .read_to_end(&mut bytes)
+                .context("Could not read from stdin")?;
+            bytes
+        }
+        Some(timeout) => {
+            // Standard input can't be read with a timeout directly, so read
+            // it on a background thread and wait for it with one instead.
+            // The reader thread is abandoned (not joined) on timeout; the
+            // process exits without waiting for it regardless.
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                let mut bytes = Vec::new();
+                let result = stdin()
+                    .take(max_size + 1)
+                    .read_to_end(&mut bytes)
+                    .map(|_| bytes);
+                let _ = sender.send(result);
+            });
+
+            receiver
+                .recv_timeout(timeout)
+                .map_err(|_| {
+                    anyhow!(
+                        "no input received on stdin within {}s; use --stdin-timeout to raise it",
+                        timeout.as_secs()
+                    )
+                })?
+                .context("Could not read from stdin")?
+        }
+    };
+
+    if bytes.len() as u64 > max_size {
+        return Err(anyhow!(
+            "input exceeds the {} byte limit; use --max-size to raise it",
+            max_size
+        ));
+    }
+
+    String::from_utf8(bytes).context("Standard input is not valid UTF-8")
+}
+
+/// Whether `err` represents a failure likely to be transient, and so worth
+/// queuing the paste for a later `patisserie retry` rather than losing it.
+///
+/// A bad API key or an oversized paste won't fix themselves, so those are
+/// not considered recoverable.
+fn is_recoverable(err: &api::Error) -> bool {
+    matches!(
+        err,
+        api::Error::Network(_) | api::Error::RateLimited { .. } | api::Error::InvalidResponse(_)
+    )
+}
+
+/// Formats the local date and time at which a paste with the given duration
+/// (in minutes) will expire.
+fn format_expiry(duration: u32) -> String {
+    const FORMAT: &[time::format_description::FormatItem] = format_description!(
+        "[year]-[month]-[day] [hour]:[minute]:[second] [offset_hour sign:mandatory]:[offset_minute]"
+    );
+
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    let expiry = now + Duration::minutes(i64::from(duration));
+
+    expiry
+        .format(FORMAT)
+        .expect("FORMAT is a well-formed format description")
+}
+
+/// Builds a short provenance header (timestamp, host, command, and
+/// patisserie version) to prepend to the content, for `--stamp`.
+fn stamp_header() -> String {
+    const FORMAT: &[time::format_description::FormatItem] = format_description!(
+        "[year]-[month]-[day] [hour]:[minute]:[second] [offset_hour sign:mandatory]:[offset_minute]"
+    );
+
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    let timestamp = now
+        .format(FORMAT)
+        .expect("FORMAT is a well-formed format description");
+
+    let host = gethostname()
+        .into_string()
+        .unwrap_or_else(|_| "unknown".to_owned());
+    let command = std::env::args().collect::<Vec<_>>().join(" ");
+
+    format!(
+        "# Pasted at {} from {} with `{}` (patisserie {})\n\n",
+        timestamp,
+        host,
+        command,
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// The result of uploading a single file, deferred so that concurrent
+/// uploads can still be printed in the order their paths were given.
+enum Outcome {
+    Uploaded {
+        paste: CreatedPaste,
+        expiry: String,
+        title: Option<String>,
+    },
+    Queued {
+        path: Utf8PathBuf,
+        err: api::Error,
+    },
+}
+
+pub fn run(
+    mut args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let (paths, expanded_a_directory) = expand_directories(args.paths)?;
+    args.paths = expand_globs(paths)?;
+
+    let stdin_is_tty = stdin().is_terminal();
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let dedup: Dedup = Mutex::new(HashMap::new());
+
+    let results = if args.paths.len() > 1 {
+        upload_many(&args, &config, &client, &dedup)
+    } else {
+        let path = args.paths.first().map(Utf8PathBuf::as_path);
+        vec![upload_one(
+            &args,
+            &config,
+            &client,
+            path,
+            stdin_is_tty,
+            &dedup,
+        )]
+    };
+
+    let create_index = args.index && args.paths.len() > 1;
+    let mut mapping = Vec::new();
+
+    for (index, result) in results.into_iter().enumerate() {
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                if args.desktop_notify {
+                    desktop::notify_failure(&err);
+                }
+                return Err(err);
+            }
+        };
+
+        if let Outcome::Uploaded { paste, .. } = &outcome {
+            if args.desktop_notify {
+                desktop::notify_success(&paste.url);
+            }
+
+            if (create_index || expanded_a_directory)
+                && let Some(path) = args.paths.get(index)
+            {
+                mapping.push((path.clone(), paste.url.clone()));
+            }
+        }
+
+        if !create_index {
+            print_outcome(&args, outcome);
+        }
+    }
+
+    if create_index {
+        let index_url = create_index_paste(&args, &config, &client, &mapping)?;
+        print_result(false, &color::url(&index_url));
+    } else if expanded_a_directory && !mapping.is_empty() {
+        println!("\nFiles uploaded:");
+        for (path, url) in mapping {
+            println!("{}\t{}", path, url);
+        }
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    Ok(())
+}
+
+/// Uploads an extra "index" paste listing every uploaded file's name and
+/// URL, for `--index`, so a multi-file upload can be shared as a single
+/// link.
+fn create_index_paste(
+    args: &Args,
+    config: &Config,
+    client: &PasteryClient,
+    mapping: &[(Utf8PathBuf, String)],
+) -> Result<String, anyhow::Error> {
+    let mut content = String::new();
+    for (path, url) in mapping {
+        content.push_str(&format!("{}\t{}\n", path, url));
+    }
+
+    let computed_duration = match args.duration.as_deref() {
+        Some(raw) => duration::resolve_duration(raw, &config.duration_aliases)?,
+        None if args.once => ONCE_DURATION,
+        None => DEFAULT_DURATION,
+    };
+
+    let paste = client
+        .create(
+            content,
+            NewPaste {
+                duration: computed_duration,
+                language: "text",
+                title: Some("Index".to_owned()),
+                max_views: None,
+            },
+        )
+        .map_err(anyhow::Error::from)
+        .context("Could not upload index paste")?;
+
+    Ok(paste.url)
+}
+
+/// Expands any directory in `paths` into the individual text files within
+/// it, recursively, honouring `.gitignore` and other `ignore`-crate rules
+/// so generated and vendored files are skipped by default.
+///
+/// Asks for confirmation on the controlling terminal before uploading more
+/// than [`DIRECTORY_CONFIRM_THRESHOLD`] files found this way, so pointing
+/// patisserie at the wrong directory cannot silently kick off a huge batch
+/// of uploads.
+///
+/// Returns the expanded paths, along with whether any directory was
+/// expanded, so the caller can print a path-to-URL mapping afterwards.
+fn expand_directories(paths: Vec<Utf8PathBuf>) -> Result<(Vec<Utf8PathBuf>, bool), anyhow::Error> {
+    let mut expanded = Vec::with_capacity(paths.len());
+    let mut expanded_a_directory = false;
+    let mut directory_matched = 0;
+
+    for path in paths {
+        if !path.is_dir() {
+            expanded.push(path);
+            continue;
+        }
+
+        expanded_a_directory = true;
+        for entry in ignore::WalkBuilder::new(&path).build() {
+            let entry = entry.with_context(|| format!("Could not walk directory `{}'", path))?;
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+
+            let file_path = Utf8PathBuf::from_path_buf(entry.into_path())
+                .map_err(|path| anyhow!("Path `{}' is not valid UTF-8", path.display()))?;
+            expanded.push(file_path);
+            directory_matched += 1;
+        }
+    }
+
+    if directory_matched > DIRECTORY_CONFIRM_THRESHOLD
+        && !confirm_directory_expansion(directory_matched)
+    {
+        return Err(anyhow!(
+            "aborted: {} files found by walking directory arguments",
+            directory_matched
+        ));
+    }
+
+    Ok((expanded, expanded_a_directory))
+}
+
+/// Asks on the controlling terminal whether to proceed with uploading
+/// `count` files found by walking a directory argument, defaulting to no if
+/// there is no controlling terminal to ask on.
+fn confirm_directory_expansion(count: usize) -> bool {
+    eprint!("This will upload {} files. Continue? [y/N] ", count);
+    if stderr().flush().is_err() {
+        return false;
+    }
+
+    let Ok(tty) = File::open(TTY_PATH) else {
+        return false;
+    };
+
+    let mut line = String::new();
+    if BufReader::new(tty).read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim(), "y" | "Y" | "yes" | "Yes")
+}
+
+/// Uploads every path in `args.paths`, bounded by `args.jobs` concurrent
+/// uploads, returning one result per path in input order regardless of the
+/// order the uploads actually finished in.
+///
+/// Shared across every worker so that `--requests-per-minute` (or the
+/// config file's `requests_per_minute`) caps the whole batch, not each
+/// worker independently.
+fn upload_many(
+    args: &Args,
+    config: &Config,
+    client: &PasteryClient,
+    dedup: &Dedup,
+) -> Vec<Result<Outcome, anyhow::Error>> {
+    let jobs = args.jobs.clamp(1, args.paths.len());
+    let next_index = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<Outcome, anyhow::Error>>>> =
+        Mutex::new(args.paths.iter().map(|_| None).collect());
+    let throttle = Mutex::new(Throttle::new(
+        args.requests_per_minute.or(config.requests_per_minute),
+    ));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+                    let Some(path) = args.paths.get(index) else {
+                        break;
+                    };
+
+                    throttle.lock().unwrap().wait();
+                    let result = upload_one(args, config, client, Some(path), false, dedup);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every index is visited by exactly one worker"))
+        .collect()
+}
+
+/// Uploads a single file (or standard input, if `path` is `None`).
+///
+/// `dedup` is consulted and updated by content hash so that, within a
+/// batch upload, a file whose content (after filtering) matches one
+/// already uploaded in this invocation reuses that paste instead of
+/// uploading it again.
+fn upload_one(
+    args: &Args,
+    config: &Config,
+    client: &PasteryClient,
+    path: Option<&Utf8Path>,
+    stdin_is_tty: bool,
+    dedup: &Dedup,
+) -> Result<Outcome, anyhow::Error> {
+    let filetype = filetype_config(config, path);
+
+    let mut tags = args.tags.clone();
+    if let Some(name) = &args.collection {
+        if !collection::exists(name)? {
+            return Err(anyhow!(
+                "Collection `{}' does not exist; create it first with `patisserie collection create {}'",
+                name,
+                name
+            ));
+        }
+        if !tags.contains(name) {
+            tags.push(name.clone());
+        }
+    }
+
+    let buffer = if let Some(path) = path {
+        read_file(path)?
+    } else {
+        let timeout = args
+            .stdin_timeout
+            .or(config.stdin_timeout_secs)
+            .map(StdDuration::from_secs);
+        read_stdin(args.max_size, timeout)?
+    };
+
+    let buffer = match &args.filter {
+        Some(pipeline) => filter::apply(pipeline, &buffer)?,
+        None => buffer,
+    };
+
+    let buffer = if args.strip_ansi || filetype.is_some_and(|filetype| filetype.strip_ansi) {
+        ansi::strip(&buffer)
+    } else {
+        buffer
+    };
+
+    let buffer = if args.anonymize {
+        anonymize::anonymize(&buffer)
+    } else {
+        buffer
+    };
+
+    let buffer = match args
+        .max_lines
+        .or(filetype.and_then(|filetype| filetype.max_lines))
+    {
+        Some(max_lines) => truncate_lines(&buffer, max_lines),
+        None => buffer,
+    };
+
+    let filetype_language = filetype
+        .and_then(|filetype| filetype.language.as_deref())
+        .map(parse_language)
+        .transpose()?;
+
+    let language = args
+        .language
+        .or(filetype_language)
+        .or_else(|| path.and_then(guess_language))
+        .unwrap_or("autodetect");
+
+    if args.check
+        && let Some(kind) = detect_check_kind(path, language)
+        && let Err(message) = check_syntax(kind, &buffer)
+    {
+        eprintln!(
+            "{}",
+            color::warning(&format!(
+                "Warning: content does not appear to be valid: {}",
+                message
+            ))
+        );
+    }
+
+    let buffer = if args.minify {
+        match detect_minify_kind(path, language) {
+            Some(kind) => minify(kind, &buffer),
+            None => buffer,
+        }
+    } else {
+        buffer
+    };
+
+    let buffer = match args.wrap {
+        Some(width) if width > 0 => wrap_lines(&buffer, width),
+        _ => buffer,
+    };
+
+    let buffer = if args.line_numbers {
+        add_line_numbers(&buffer)
+    } else {
+        buffer
+    };
+
+    let buffer = if args.stamp {
+        format!("{}{}", stamp_header(), buffer)
+    } else {
+        buffer
+    };
+
+    if let Some(tee) = &args.tee {
+        write_tee(tee, &buffer)?;
+    }
+
+    if let Some(command) = &config.pre_upload_hook {
+        hooks::run_pre_upload(command, buffer.as_bytes())?;
+    }
+
+    let computed_duration = match args
+        .duration
+        .as_deref()
+        .or_else(|| filetype.and_then(|filetype| filetype.duration.as_deref()))
+    {
+        Some(raw) => duration::resolve_duration(raw, &config.duration_aliases)?,
+        None if args.once => ONCE_DURATION,
+        None => DEFAULT_DURATION,
+    };
+
+    let max_views = if args.once && args.max_views.is_none() {
+        Some(1)
+    } else {
+        match args.max_views {
+            Some(0) => None,
+            Some(max_views) => Some(max_views),
+            None => config.max_views,
+        }
+    };
+
+    let title = args
+        .title
+        .clone()
+        .or_else(|| {
+            if args.title_from_content {
+                title_from_content(&buffer)
+            } else {
+                None
+            }
+        })
+        .or_else(|| path.and_then(Utf8Path::file_name).map(ToOwned::to_owned))
+        .or_else(|| {
+            if stdin_is_tty && path.is_none() {
+                prompt_for_title()
+            } else {
+                None
+            }
+        });
+
+    let title_for_webhook = title.clone();
+    let original = args.verify.then(|| buffer.clone());
+    let archived = (args.archive || config.archive_uploads).then(|| buffer.clone());
+    let queued = QueuedPaste {
+        body: buffer.clone(),
+        duration: computed_duration,
+        language: language.to_owned(),
+        title: title.clone(),
+        max_views,
+        tags: tags.clone(),
+    };
+
+    if args.preview_highlight {
+        let rendered = highlight::highlight(&buffer, language);
+        if !confirm_preview(&rendered) {
+            return Err(anyhow!("aborted: upload cancelled by user"));
+        }
+    } else if args.preview_render && language == "markdown" {
+        let rendered = termimad::term_text(&buffer).to_string();
+        if !confirm_preview(&rendered) {
+            return Err(anyhow!("aborted: upload cancelled by user"));
+        }
+    }
+
+    let content_hash = history::content_hash(&buffer);
+    let content_len = buffer.len();
+
+    // Reserving the slot (as `None`) before uploading, rather than only
+    // inserting the result afterwards, means two workers racing on
+    // identical content serialize on this hash's mutex instead of both
+    // missing the cache and both hitting the network.
+    let slot = dedup
+        .lock()
+        .unwrap()
+        .entry(content_hash.clone())
+        .or_insert_with(|| Arc::new(Mutex::new(None)))
+        .clone();
+    let mut slot = slot.lock().unwrap();
+
+    let paste = if let Some(paste) = slot.clone() {
+        paste
+    } else {
+        let payload_bytes = buffer.len();
+        let spinner = Spinner::start(&format!(
+            "Uploading {}",
+            Spinner::format_size(payload_bytes)
+        ));
+        let result = if args.timings {
+            let (result, timings) = timings::measure(payload_bytes, || {
+                client.create(
+                    buffer,
+                    NewPaste {
+                        duration: computed_duration,
+                        language,
+                        title,
+                        max_views,
+                    },
+                )
+            })?;
+            eprintln!("{}", timings);
+            result
+        } else {
+            client.create(
+                buffer,
+                NewPaste {
+                    duration: computed_duration,
+                    language,
+                    title,
+                    max_views,
+                },
+            )
+        };
+        drop(spinner);
+
+        match result {
+            Ok(paste) => {
+                *slot = Some(paste.clone());
+                paste
+            }
+            Err(err) if is_recoverable(&err) && (args.offline_queue || confirm_save_to_queue()) => {
+                let queue_path = queue::enqueue(&queued)?;
+                return Ok(Outcome::Queued {
+                    path: queue_path,
+                    err,
+                });
+            }
+            Err(err) => {
+                let err = anyhow::Error::from(err);
+                return Err(match path {
+                    Some(path) => err.context(format!("Could not upload file `{}'", path)),
+                    None => err,
+                });
+            }
+        }
+    };
+
+    audit::record(
+        config,
+        "paste",
+        &paste.url,
+        Some(content_len),
+        Some(&content_hash),
+    )?;
+
+    history::record(&HistoryEntry {
+        hash: content_hash,
+        id: paste.id.clone(),
+        url: paste.url.clone(),
+        tags: tags.clone(),
+        note: None,
+        title: title_for_webhook.clone(),
+        expires_at: Some(history::expiry_timestamp(computed_duration)),
+    })?;
+
+    if let Some(original) = original {
+        api::verify(client, &paste.id, &original)?;
+    }
+
+    if let Some(content) = archived {
+        archive::store(&paste.id, &content)?;
+    }
+
+    let expiry = format_expiry(computed_duration);
+
+    if let Some(webhook_url) = args.webhook.as_deref().or(config.webhook_url.as_deref()) {
+        webhook::notify(
+            webhook_url,
+            &webhook::Notification {
+                url: &paste.url,
+                title: title_for_webhook.as_deref(),
+                language,
+                expiry: &expiry,
+            },
+        )?;
+    }
+
+    if let Some(command) = &config.post_upload_hook {
+        hooks::run_post_upload(command, &paste.url)?;
+    }
+
+    if let Some(template) = &config.on_success {
+        let command = render_on_success_template(
+            template,
+            &paste,
+            title_for_webhook.as_deref(),
+            language,
+            &expiry,
+        );
+        if let Err(err) = shell::command(&command).status() {
+            eprintln!(
+                "{}",
+                color::warning(&format!(
+                    "Warning: could not run on_success `{}': {}",
+                    command, err
+                ))
+            );
+        }
+    }
+
+    if let Some(target) = &args.notify {
+        let text = match &title_for_webhook {
+            Some(title) => format!("{}: {}", title, paste.url),
+            None => paste.url.clone(),
+        };
+
+        match target {
+            NotifyTarget::Slack(channel) => {
+                let webhook_url = config.slack_webhook_url.as_deref().ok_or_else(|| {
+                    anyhow!(
+                        "--notify slack:... requires `slack_webhook_url' to be set in the config file"
+                    )
+                })?;
+                slack::notify(webhook_url, channel, &text)?;
+            }
+            NotifyTarget::Matrix(room_id) => {
+                let homeserver_url = config.matrix_homeserver_url.as_deref().ok_or_else(|| {
+                    anyhow!(
+                        "--notify matrix:... requires `matrix_homeserver_url' to be set in the config file"
+                    )
+                })?;
+                let access_token = config.matrix_access_token.as_deref().ok_or_else(|| {
+                    anyhow!(
+                        "--notify matrix:... requires `matrix_access_token' to be set in the config file"
+                    )
+                })?;
+                matrix::notify(homeserver_url, access_token, room_id, &text)?;
+            }
+        }
+    }
+
+    if let Some(to) = &args.email {
+        email::send(config, to, title_for_webhook.as_deref(), &paste.url)?;
+    }
+
+    if args.copy {
+        let backend = config
+            .clipboard_backend
+            .as_deref()
+            .map(clipboard::Backend::parse)
+            .transpose()?;
+        clipboard::copy(backend, &paste.url);
+    }
+
+    if args.tmux {
+        clipboard::set_tmux_buffer(&paste.url);
+    }
+
+    if args.open {
+        let browser = args.browser.as_deref().or(config.browser.as_deref());
+        browser::open(&paste.url, browser);
+    }
+
+    Ok(Outcome::Uploaded {
+        paste,
+        expiry,
+        title: title_for_webhook,
+    })
+}
+
+/// Prints the result of a single upload, in whichever of the human,
+/// `--json`, `--porcelain`, or `--format-preset` formats was requested.
+fn print_outcome(args: &Args, outcome: Outcome) {
+    let (paste, expiry, title) = match outcome {
+        Outcome::Queued { path, err } => {
+            eprintln!(
+                "Could not upload paste ({}); queued for later retry at `{}'. Run `patisserie retry` to resubmit it.",
+                err, path
+            );
+            return;
+        }
+        Outcome::Uploaded {
+            paste,
+            expiry,
+            title,
+        } => (paste, expiry, title),
+    };
+
+    let raw_url = api::raw_url(&paste.url);
+    let tee_to_stdout = args.tee.as_deref().is_some_and(|target| target == "-");
+
+    if let Some(preset) = args.format_preset {
+        print_result(
+            tee_to_stdout,
+            &format_for_preset(preset, title.as_deref(), &paste.url),
+        );
+        return;
+    }
+
+    if args.json {
+        let output = serde_json::json!({
+            "id": paste.id,
+            "url": paste.url,
+            "raw_url": raw_url,
+            "expires": expiry,
+        });
+        print_result(tee_to_stdout, &output.to_string());
+        return;
+    }
+
+    if args.porcelain {
+        print_result(
+            tee_to_stdout,
+            &porcelain::line(&[&paste.id, &paste.url, &raw_url, &expiry]),
+        );
+        return;
+    }
+
+    if args.id_only {
+        print_result(tee_to_stdout, &paste.id);
+    } else {
+        let url = if args.raw_url { &raw_url } else { &paste.url };
+        print_result(tee_to_stdout, &color::url(url));
+    }
+
+    if args.once {
+        eprintln!(
+            "{}",
+            i18n::message("once-view-note", &fluent::FluentArgs::new())
+        );
+    }
+
+    eprintln!("Expires: {}", expiry);
+}
+
+/// Prints an upload result line, moving it to standard error instead of
+/// standard output when `--tee` is already using stdout for the content
+/// itself.
+fn print_result(tee_to_stdout: bool, line: &str) {
+    if tee_to_stdout {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
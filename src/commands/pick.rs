@@ -0,0 +1,222 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, anyhow};
+use clap::Args as ClapArgs;
+
+use crate::archive;
+use crate::browser;
+use crate::clipboard;
+use crate::config::Config;
+use crate::duration::ONE_DAY;
+use crate::history::{self, HistoryEntry};
+use patisserie::api::{NewPaste, PasteryClient};
+
+const OPEN_KEY: &str = "ctrl-o";
+const RE_PASTE_KEY: &str = "ctrl-r";
+
+/// Fuzzy-pick a paste from local history with `fzf` or `sk`, then copy,
+/// open, or re-paste it.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The fuzzy-finder command to run.
+    ///
+    /// If not provided, `fzf` is used if it is on `$PATH`, falling back to
+    /// `sk` (skim).
+    #[arg(long)]
+    finder: Option<String>,
+
+    /// The browser command used when opening the picked paste with
+    /// Ctrl-O, e.g. `firefox`.
+    ///
+    /// Falls back to the `browser` set in the config file, then the
+    /// `BROWSER` environment variable, then the system default browser.
+    #[arg(long)]
+    browser: Option<String>,
+}
+
+fn is_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+fn detect_finder() -> Option<&'static str> {
+    ["fzf", "sk"]
+        .into_iter()
+        .find(|program| is_on_path(program))
+}
+
+/// Runs `finder` over one line per history entry, returning the key that
+/// was pressed to select it (empty for the default Enter) and the selected
+/// entry, or `None` if nothing was selected (e.g. Esc or Ctrl-C).
+fn run_finder<'a>(
+    finder: &str,
+    entries: &'a [HistoryEntry],
+) -> Result<Option<(String, &'a HistoryEntry)>, anyhow::Error> {
+    let lines: Vec<String> = entries
+        .iter()
+        .rev()
+        .map(|entry| {
+            let title = entry.title.as_deref().unwrap_or("untitled");
+            let tags = if entry.tags.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", entry.tags.join(", "))
+            };
+            format!("{}\t{}\t{}{}", entry.id, entry.url, title, tags)
+        })
+        .collect();
+
+    let mut child = Command::new(finder)
+        .arg("--delimiter=\t")
+        .arg("--with-nth=2..")
+        .arg(format!("--expect={},{}", OPEN_KEY, RE_PASTE_KEY))
+        .arg("--header=Enter: copy URL  Ctrl-O: open  Ctrl-R: re-paste")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run `{}'", finder))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(lines.join("\n").as_bytes())
+            .with_context(|| format!("Could not write to `{}'", finder))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Could not wait for `{}' to exit", finder))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .with_context(|| format!("`{}' produced non-UTF-8 output", finder))?;
+
+    let mut result_lines = stdout.lines();
+    let key = result_lines.next().unwrap_or("").to_owned();
+    let Some(selected) = result_lines.next() else {
+        return Ok(None);
+    };
+
+    let id = selected
+        .split('\t')
+        .next()
+        .ok_or_else(|| anyhow!("Could not parse the entry picked by `{}'", finder))?;
+
+    let entry = entries
+        .iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| anyhow!("Could not find a history entry for `{}'", id))?;
+
+    Ok(Some((key, entry)))
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let entries = history::list()?;
+    if entries.is_empty() {
+        return Err(anyhow!("No pastes in history to pick from."));
+    }
+
+    let finder = match args.finder {
+        Some(finder) => finder,
+        None => detect_finder()
+            .ok_or_else(|| {
+                anyhow!("Could not find `fzf' or `sk' on $PATH; install one, or pass --finder")
+            })?
+            .to_owned(),
+    };
+
+    let Some((key, entry)) = run_finder(&finder, &entries)? else {
+        return Ok(());
+    };
+
+    match key.as_str() {
+        OPEN_KEY => {
+            let browser = args.browser.as_deref().or(config.browser.as_deref());
+            browser::open(&entry.url, browser);
+        }
+        RE_PASTE_KEY => re_paste(entry, &config, api_key, verbose)?,
+        _ => {
+            let backend = config
+                .clipboard_backend
+                .as_deref()
+                .map(clipboard::Backend::parse)
+                .transpose()?;
+            clipboard::copy(backend, &entry.url);
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-uploads the archived content behind `entry` as a brand new paste.
+fn re_paste(
+    entry: &HistoryEntry,
+    config: &Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let content = archive::retrieve(&entry.id)?.ok_or_else(|| {
+        anyhow!(
+            "No local archive found for `{}'; re-pasting requires the original upload to have \
+             used `--archive'",
+            entry.id
+        )
+    })?;
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let paste = client
+        .create(
+            content.clone(),
+            NewPaste {
+                duration: ONE_DAY,
+                language: "autodetect",
+                title: entry.title.clone(),
+                max_views: None,
+            },
+        )
+        .with_context(|| format!("Could not re-paste `{}'", entry.id))?;
+
+    history::record(&HistoryEntry {
+        hash: history::content_hash(&content),
+        id: paste.id.clone(),
+        url: paste.url.clone(),
+        tags: entry.tags.clone(),
+        note: None,
+        title: entry.title.clone(),
+        expires_at: Some(history::expiry_timestamp(ONE_DAY)),
+    })?;
+
+    archive::store(&paste.id, &content)?;
+
+    println!("{}", paste.url);
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::time::Instant;
+
+use anyhow::anyhow;
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::porcelain;
+use patisserie::api::{self, PasteryClient};
+
+/// Check reachability and latency of pastery.net, for use as a health check
+/// in monitoring scripts.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Also check that the API key is valid, failing if it isn't.
+    ///
+    /// Without this, a rejected API key still counts as pastery.net being
+    /// reachable.
+    #[arg(long)]
+    auth: bool,
+
+    /// Print a stable, tab-separated line (status, latency_ms, error)
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(args: Args, config: Config, api_key: String) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let start = Instant::now();
+    let result = client.list();
+    let elapsed_ms = start.elapsed().as_millis();
+
+    let (status, err) = match result {
+        Ok(_) => ("up", None),
+        Err(api::Error::Network(_)) => ("down", None),
+        Err(err) => ("up", Some(err)),
+    };
+
+    if args.porcelain {
+        println!(
+            "{}",
+            porcelain::line(&[
+                status,
+                &elapsed_ms.to_string(),
+                &err.as_ref().map_or_else(String::new, ToString::to_string),
+            ])
+        );
+    } else {
+        println!("pastery.net is {} ({}ms)", status, elapsed_ms);
+        if let Some(err) = &err {
+            println!("{}", err);
+        }
+    }
+
+    if status == "down" {
+        return Err(anyhow!("pastery.net is unreachable"));
+    }
+
+    if args.auth
+        && let Some(err) = err
+    {
+        return Err(err.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Context, anyhow};
+use clap::Args as ClapArgs;
+use time::OffsetDateTime;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_MINUTE};
+use crate::porcelain;
+use crate::throttle::Throttle;
+use patisserie::api::PasteryClient;
+
+/// Bulk-delete pastes on your account.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Only delete pastes older than this duration (e.g. `30d`).
+    #[arg(long = "older-than")]
+    older_than: Option<String>,
+
+    /// Delete every paste on the account, regardless of age.
+    #[arg(long, conflicts_with = "older_than")]
+    all: bool,
+
+    /// Show what would be deleted, without deleting anything.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Print a stable, tab-separated line (id, action, url) per paste
+    /// instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+
+    /// The maximum number of delete requests to make per minute.
+    ///
+    /// If not provided, this falls back to the `requests_per_minute` set in
+    /// the config file, if any. Otherwise, deletes are not throttled.
+    #[arg(long)]
+    requests_per_minute: Option<u32>,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    if !args.all && args.older_than.is_none() {
+        return Err(anyhow!("You must specify either --older-than or --all"));
+    }
+
+    let threshold_minutes = args
+        .older_than
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?;
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let pastes = client.list().context("Could not list pastes")?;
+    crate::commands::report_rate_limit(&client, verbose);
+    let now = OffsetDateTime::now_utc();
+
+    let mut to_delete = Vec::new();
+    for paste in pastes {
+        let matches = match threshold_minutes {
+            None => true,
+            Some(threshold_minutes) => {
+                let Ok(created) = OffsetDateTime::parse(
+                    &paste.creation_date,
+                    &time::format_description::well_known::Rfc3339,
+                ) else {
+                    continue;
+                };
+
+                let age_minutes = (now - created).whole_minutes().max(0) as u32 / ONE_MINUTE;
+                age_minutes >= threshold_minutes
+            }
+        };
+
+        if matches {
+            to_delete.push(paste);
+        }
+    }
+
+    if to_delete.is_empty() {
+        if !args.porcelain {
+            println!("No pastes matched.");
+        }
+        return Ok(());
+    }
+
+    let mut throttle = Throttle::new(args.requests_per_minute.or(config.requests_per_minute));
+
+    for paste in &to_delete {
+        let action = if args.dry_run {
+            "would-delete"
+        } else {
+            "delete"
+        };
+
+        if !args.dry_run {
+            throttle.wait();
+            client
+                .delete(&paste.id)
+                .with_context(|| format!("Could not delete paste `{}'", paste.id))?;
+        }
+
+        if args.porcelain {
+            println!("{}", porcelain::line(&[&paste.id, action, &paste.url]));
+        } else {
+            let title = paste.title.as_deref().unwrap_or("(untitled)");
+            let verb = if args.dry_run {
+                "Would delete"
+            } else {
+                "Deleted"
+            };
+            println!("{} {} ({})", verb, paste.url, title);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::Context;
+use clap::Args as ClapArgs;
+
+use crate::audit;
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::history;
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient, extract_id};
+
+/// Extend a paste's lifetime by re-creating it with a fresh duration.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the paste to renew, or a shorthand history
+    /// reference: `last`, `~N`, or a bare `N` for the Nth most recent
+    /// upload.
+    reference: String,
+
+    /// The new duration that the renewed paste will live for.
+    ///
+    /// Accepts the same syntax as `patisserie paste --duration`. Defaults to
+    /// 1d if not given.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// Delete the original paste once the renewed one has been created.
+    #[arg(long)]
+    delete_original: bool,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let resolved = history::resolve_id(&args.reference)?;
+    let id = extract_id(&resolved);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let original = client
+        .get(id)
+        .with_context(|| format!("Could not fetch paste `{}'", args.reference))?;
+
+    let new_duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let content_len = original.body.len();
+    let content_hash = history::content_hash(&original.body);
+
+    let renewed = client.create(
+        original.body,
+        NewPaste {
+            duration: new_duration,
+            language: &original.language,
+            title: original.title,
+            max_views: None,
+        },
+    )?;
+
+    if args.delete_original {
+        client
+            .delete(id)
+            .with_context(|| format!("Could not delete original paste `{}'", args.reference))?;
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    audit::record(
+        &config,
+        "renew",
+        &renewed.url,
+        Some(content_len),
+        Some(&content_hash),
+    )?;
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&renewed.url);
+        println!(
+            "{}",
+            porcelain::line(&[&renewed.id, &renewed.url, &raw_url])
+        );
+    } else {
+        println!("{}", renewed.url);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs;
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::porcelain;
+use crate::throttle::Throttle;
+use patisserie::api::{NewPaste, PasteryClient};
+
+/// Re-upload every paste from a directory created by `backup`.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The directory to read pastes from.
+    directory: Utf8PathBuf,
+
+    /// The duration that each restored paste will live for.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// Print a stable, tab-separated line (old_url, new_id, new_url) per
+    /// paste instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+
+    /// The maximum number of upload requests to make per minute.
+    ///
+    /// If not provided, this falls back to the `requests_per_minute` set in
+    /// the config file, if any. Otherwise, uploads are not throttled.
+    #[arg(long)]
+    requests_per_minute: Option<u32>,
+}
+
+/// The metadata sidecar written by `backup`, as much of it as `restore` uses.
+#[derive(Deserialize)]
+struct Metadata {
+    url: Option<String>,
+    title: Option<String>,
+    language: Option<String>,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let mut entries: Vec<Utf8PathBuf> = fs::read_dir(&args.directory)
+        .with_context(|| format!("Could not read directory `{}'", args.directory))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Utf8PathBuf::try_from(entry.path()).ok())
+        .filter(|path| path.extension() == Some("txt"))
+        .collect();
+    entries.sort();
+
+    let mut throttle = Throttle::new(args.requests_per_minute.or(config.requests_per_minute));
+
+    for content_path in entries {
+        throttle.wait();
+
+        let body = fs::read_to_string(&content_path)
+            .with_context(|| format!("Could not read file `{}'", content_path))?;
+
+        let metadata_path = content_path.with_extension("json");
+        let metadata = fs::read_to_string(&metadata_path)
+            .ok()
+            .map(|raw| {
+                serde_json::from_str::<Metadata>(&raw)
+                    .with_context(|| format!("Could not parse metadata file `{}'", metadata_path))
+            })
+            .transpose()?;
+
+        let title = metadata.as_ref().and_then(|m| m.title.clone());
+        let language = metadata
+            .as_ref()
+            .and_then(|m| m.language.clone())
+            .unwrap_or_else(|| "autodetect".to_owned());
+        let old_url = metadata
+            .as_ref()
+            .and_then(|m| m.url.clone())
+            .unwrap_or_else(|| content_path.to_string());
+
+        let restored = client.create(
+            body,
+            NewPaste {
+                duration,
+                language: &language,
+                title,
+                max_views: None,
+            },
+        )?;
+
+        if args.porcelain {
+            println!(
+                "{}",
+                porcelain::line(&[&old_url, &restored.id, &restored.url])
+            );
+        } else {
+            println!("{} -> {}", old_url, restored.url);
+        }
+    }
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use clap::Args as ClapArgs;
+
+use crate::archive;
+use crate::color;
+use crate::config::Config;
+use crate::history;
+use crate::pager;
+use crate::porcelain;
+
+/// Search the local archive of previously uploaded content.
+///
+/// Only pastes uploaded with `--archive` (or `archive_uploads` set in the
+/// config file) are searchable; nothing is fetched from pastery.net.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The text to search for.
+    query: String,
+
+    /// Match case-insensitively.
+    #[arg(short, long)]
+    ignore_case: bool,
+
+    /// Print a stable, tab-separated line (id, url, line_number, line) per
+    /// match instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+/// Returns the byte ranges in `line` at which `query` occurs, matching
+/// case-insensitively if `ignore_case` is set.
+fn find_matches(line: &str, query: &str, ignore_case: bool) -> Vec<(usize, usize)> {
+    let (haystack, needle) = if ignore_case {
+        (line.to_lowercase(), query.to_lowercase())
+    } else {
+        (line.to_owned(), query.to_owned())
+    };
+
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut rest = haystack.as_str();
+    let mut offset = 0;
+    while let Some(index) = rest.find(&needle) {
+        let start = offset + index;
+        let end = start + needle.len();
+        matches.push((start, end));
+        offset = end;
+        rest = &haystack[offset..];
+    }
+
+    matches
+}
+
+/// Wraps every occurrence of `query` in `line` with [`color::highlight_match`].
+fn highlight_line(line: &str, query: &str, ignore_case: bool) -> String {
+    let matches = find_matches(line, query, ignore_case);
+
+    let mut highlighted = String::with_capacity(line.len());
+    let mut cursor = 0;
+    for (start, end) in matches {
+        highlighted.push_str(&line[cursor..start]);
+        highlighted.push_str(&color::highlight_match(&line[start..end]));
+        cursor = end;
+    }
+    highlighted.push_str(&line[cursor..]);
+
+    highlighted
+}
+
+pub fn run(args: Args, _config: Config) -> Result<(), anyhow::Error> {
+    let entries = history::list()?;
+
+    let mut output = String::new();
+    let mut found_any = false;
+
+    for entry in entries.iter().rev() {
+        let Some(content) = archive::retrieve(&entry.id)? else {
+            continue;
+        };
+
+        for (line_number, line) in content.lines().enumerate() {
+            if find_matches(line, &args.query, args.ignore_case).is_empty() {
+                continue;
+            }
+
+            found_any = true;
+
+            if args.porcelain {
+                println!(
+                    "{}",
+                    porcelain::line(
+                        &[&entry.id, &entry.url, &(line_number + 1).to_string(), line,]
+                    )
+                );
+            } else {
+                output.push_str(&format!(
+                    "{} ({}):{}: {}\n",
+                    entry.url,
+                    entry.title.as_deref().unwrap_or("untitled"),
+                    line_number + 1,
+                    highlight_line(line, &args.query, args.ignore_case)
+                ));
+            }
+        }
+    }
+
+    if args.porcelain {
+        return Ok(());
+    }
+
+    if !found_any {
+        output.push_str("No matches in the local archive.\n");
+    }
+
+    pager::page(&output)
+}
@@ -0,0 +1,153 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+
+use anyhow::Context;
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::language::parse_language;
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient};
+
+const DEFAULT_LANGUAGE: &str = "diff";
+const DEFAULT_COVER_TITLE: &str = "Patch series";
+
+/// Upload a series of patch files in order, each tagged with its position
+/// in the series, plus a cover paste indexing every part.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The patch files to upload, in series order.
+    #[arg(required = true)]
+    paths: Vec<Utf8PathBuf>,
+
+    /// The language for each patch paste.
+    #[arg(short, long = "lang", value_parser = parse_language, default_value = DEFAULT_LANGUAGE)]
+    language: &'static str,
+
+    /// The duration that every paste in the series (including the cover)
+    /// will live for. Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The title for the cover paste. Defaults to "Patch series".
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) for the cover
+    /// paste instead of human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+/// Prepends a `Part N of M: <filename>` header to `body`, so each patch in
+/// the series stands on its own when shared out of context.
+fn with_part_header(index: usize, total: usize, path: &Utf8PathBuf, body: &str) -> String {
+    format!("Part {} of {}: {}\n\n{}", index + 1, total, path, body)
+}
+
+/// Uploads a cover paste indexing every part of the series by filename and
+/// URL, mirroring `paste --index`'s mapping paste.
+fn create_cover_paste(
+    args: &Args,
+    client: &PasteryClient,
+    duration: u32,
+    mapping: &[(Utf8PathBuf, String)],
+) -> Result<api::CreatedPaste, anyhow::Error> {
+    let mut content = format!(
+        "{} ({} parts):\n\n",
+        args.title.as_deref().unwrap_or(DEFAULT_COVER_TITLE),
+        mapping.len()
+    );
+    for (index, (path, url)) in mapping.iter().enumerate() {
+        content.push_str(&format!("{}. {}\t{}\n", index + 1, path, url));
+    }
+
+    client
+        .create(
+            content,
+            NewPaste {
+                duration,
+                language: "text",
+                title: Some(
+                    args.title
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_COVER_TITLE.to_owned()),
+                ),
+                max_views: None,
+            },
+        )
+        .map_err(anyhow::Error::from)
+        .context("Could not upload cover paste")
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let client = PasteryClient::new(api_key, config.connection_options());
+
+    let duration = args
+        .duration
+        .as_deref()
+        .map(|raw| duration::resolve_duration(raw, &config.duration_aliases))
+        .transpose()?
+        .unwrap_or(ONE_DAY);
+
+    let total = args.paths.len();
+    let mut mapping = Vec::with_capacity(total);
+
+    for (index, path) in args.paths.iter().enumerate() {
+        let body = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read file `{}'", path))?;
+        let content = with_part_header(index, total, path, &body);
+
+        let title = path.file_name().map(ToOwned::to_owned);
+
+        let paste = client
+            .create(
+                content,
+                NewPaste {
+                    duration,
+                    language: args.language,
+                    title,
+                    max_views: None,
+                },
+            )
+            .with_context(|| format!("Could not upload patch `{}'", path))?;
+
+        println!("{}\t{}", path, paste.url);
+        mapping.push((path.clone(), paste.url));
+    }
+
+    let cover = create_cover_paste(&args, &client, duration, &mapping)?;
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&cover.url);
+        println!("{}", porcelain::line(&[&cover.id, &cover.url, &raw_url]));
+    } else {
+        println!("\nCover: {}", cover.url);
+    }
+
+    Ok(())
+}
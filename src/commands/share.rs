@@ -0,0 +1,76 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Prints a ready-to-send message for a previously uploaded paste, built
+//! from its recorded history, for pasting into Slack, IRC, or a
+//! Markdown-aware chat.
+
+use anyhow::anyhow;
+use clap::Args as ClapArgs;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::config::Config;
+use crate::history::{self, HistoryEntry};
+
+/// Print a ready-to-send message summarizing a previously uploaded paste.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The id or URL of the paste to share, or a shorthand history
+    /// reference: `last`, `~N`, or a bare `N` for the Nth most recent
+    /// upload.
+    reference: String,
+}
+
+pub fn run(args: Args, _config: Config, _api_key: String) -> Result<(), anyhow::Error> {
+    let entry = history::resolve(&args.reference)?
+        .ok_or_else(|| anyhow!("No history entry found for `{}'", args.reference))?;
+
+    println!("{}", format_message(&entry));
+
+    Ok(())
+}
+
+/// Formats `entry` as a ready-to-send message, e.g. "`build.log` (expires
+/// in 23h): <url>".
+fn format_message(entry: &HistoryEntry) -> String {
+    let label = match &entry.title {
+        Some(title) => format!("`{}`", title),
+        None => format!("`{}`", entry.id),
+    };
+
+    match entry.expires_at.as_deref().and_then(time_remaining) {
+        Some(remaining) => format!("{} (expires in {}): {}", label, remaining, entry.url),
+        None => format!("{}: {}", label, entry.url),
+    }
+}
+
+/// Formats the time remaining until `expires_at`, or `None` if it has
+/// already passed or could not be parsed.
+fn time_remaining(expires_at: &str) -> Option<String> {
+    let remaining = OffsetDateTime::parse(expires_at, &Rfc3339).ok()? - OffsetDateTime::now_utc();
+
+    if remaining.is_negative() {
+        return None;
+    }
+
+    Some(if remaining.whole_hours() > 0 {
+        format!("{}h", remaining.whole_hours())
+    } else {
+        format!("{}m", remaining.whole_minutes().max(1))
+    })
+}
@@ -0,0 +1,216 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write, stderr};
+
+use camino::Utf8Path;
+use clap::Args as ClapArgs;
+
+use anyhow::{Context, anyhow};
+
+use crate::config::Config;
+use crate::duration::{self, ONE_DAY};
+use crate::language::{guess_language, parse_language};
+use crate::porcelain;
+use patisserie::api::{self, NewPaste, PasteryClient};
+
+#[cfg(unix)]
+const TTY_PATH: &str = "/dev/tty";
+#[cfg(windows)]
+const TTY_PATH: &str = "CON";
+
+/// Paste a named template file, expanding `{{var}}` placeholders.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The name of the template, relative to the templates directory (e.g.
+    /// `~/.config/patisserie/templates/bug-report` on Linux).
+    name: String,
+
+    /// A `key=value` pair to substitute for a `{{key}}` placeholder in the
+    /// template.
+    ///
+    /// Can be given more than once, e.g. `--var version=1.2 --var
+    /// reporter=beth`. Any placeholder without a corresponding `--var` is
+    /// prompted for on the controlling terminal.
+    #[arg(long = "var", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+
+    /// The duration that this paste will live for. Defaults to 1d.
+    #[arg(short, long = "duration")]
+    duration: Option<String>,
+
+    /// The language for the paste.
+    ///
+    /// If not provided, patisserie will attempt to guess based on the
+    /// template's file extension.
+    #[arg(short, long = "lang", value_parser = parse_language)]
+    language: Option<&'static str>,
+
+    /// The title of the paste. Defaults to the template's name.
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// Print a stable, tab-separated line (id, url, raw_url) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+/// Parses a `--var key=value` pair.
+fn parse_var(s: &str) -> Result<(String, String), anyhow::Error> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected `KEY=VALUE', got `{}'", s))?;
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Prompts for the value of a missing template variable on the controlling
+/// terminal, in the style of `paste::prompt_for_title`.
+fn prompt_for_var(name: &str) -> Option<String> {
+    eprint!("{}: ", name);
+    stderr().flush().ok()?;
+
+    let tty = File::open(TTY_PATH).ok()?;
+    let mut line = String::new();
+    BufReader::new(tty).read_line(&mut line).ok()?;
+
+    let value = line.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_owned())
+    }
+}
+
+/// Returns the names of every `{{name}}` placeholder in `template`, in the
+/// order they first appear, without duplicates.
+fn placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+
+        let name = after[..end].trim().to_owned();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+        rest = &after[end + 2..];
+    }
+
+    names
+}
+
+/// Substitutes every `{{name}}` placeholder in `template` with its value
+/// from `vars`, leaving a placeholder untouched if it has no value.
+fn expand(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match vars.get(name) {
+                    Some(value) => output.push_str(value),
+                    None => output.push_str(&after[..end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str("{{");
+                rest = after;
+                break;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let dir = Config::templates_dir()
+        .ok_or_else(|| anyhow!("Could not determine the templates directory"))?;
+    let path = dir.join(&args.name);
+
+    let template = std::fs::read_to_string(&path)
+        .with_context(|| format!("Could not read template `{}'", path))?;
+
+    let mut vars: HashMap<String, String> = args.vars.into_iter().collect();
+    for name in placeholders(&template) {
+        if vars.contains_key(&name) {
+            continue;
+        }
+
+        let value = prompt_for_var(&name)
+            .ok_or_else(|| anyhow!("No value given for template variable `{{{{{}}}}}'", name))?;
+        vars.insert(name, value);
+    }
+
+    let content = expand(&template, &vars);
+
+    let language = args
+        .language
+        .or_else(|| guess_language(Utf8Path::new(&args.name)))
+        .unwrap_or("autodetect");
+
+    let computed_duration = match args.duration.as_deref() {
+        Some(raw) => duration::resolve_duration(raw, &config.duration_aliases)?,
+        None => ONE_DAY,
+    };
+
+    let title = args.title.or_else(|| Some(args.name.clone()));
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let paste = client
+        .create(
+            content,
+            NewPaste {
+                duration: computed_duration,
+                language,
+                title,
+                max_views: None,
+            },
+        )
+        .map_err(anyhow::Error::from)
+        .with_context(|| format!("Could not upload template `{}'", args.name))?;
+
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let raw_url = api::raw_url(&paste.url);
+        println!("{}", porcelain::line(&[&paste.id, &paste.url, &raw_url]));
+    } else {
+        println!("{}", paste.url);
+    }
+
+    Ok(())
+}
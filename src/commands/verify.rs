@@ -0,0 +1,86 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs::File;
+use std::io::{Read, stdin};
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use clap::Args as ClapArgs;
+
+use crate::color;
+use crate::config::Config;
+use crate::i18n;
+use crate::porcelain;
+use patisserie::api::{self, PasteryClient, extract_id};
+
+/// Fetch a paste and compare it byte-for-byte against local content.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The URL or id of the paste to verify.
+    reference: String,
+
+    /// The path of the file to compare against.
+    ///
+    /// If not provided, the content will be read from standard input.
+    path: Option<Utf8PathBuf>,
+
+    /// Print a stable, tab-separated line (id, result) instead of
+    /// human-facing output.
+    #[arg(long)]
+    porcelain: bool,
+}
+
+pub fn run(
+    args: Args,
+    config: Config,
+    api_key: String,
+    verbose: bool,
+) -> Result<(), anyhow::Error> {
+    let id = extract_id(&args.reference);
+
+    let mut expected = String::new();
+    if let Some(path) = &args.path {
+        File::open(path)
+            .with_context(|| format!("Could not open file `{}' for reading", path))?
+            .read_to_string(&mut expected)
+            .with_context(|| format!("Could not read file `{}'", path))?;
+    } else {
+        stdin()
+            .read_to_string(&mut expected)
+            .context("Could not read from stdin")?;
+    }
+
+    let client = PasteryClient::new(api_key, config.connection_options());
+    let result = api::verify(&client, id, &expected);
+    crate::commands::report_rate_limit(&client, verbose);
+
+    if args.porcelain {
+        let outcome = if result.is_ok() { "match" } else { "mismatch" };
+        println!("{}", porcelain::line(&[id, outcome]));
+        Ok(result?)
+    } else {
+        result?;
+        let mut message_args = fluent::FluentArgs::new();
+        message_args.set("reference", args.reference.clone());
+        println!(
+            "{}",
+            color::success(&i18n::message("verify-matches", &message_args))
+        );
+        Ok(())
+    }
+}
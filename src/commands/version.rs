@@ -0,0 +1,54 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use clap::Args as ClapArgs;
+
+use crate::config::Config;
+use crate::release;
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Print the running version.
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Also check GitHub for a newer release, and print upgrade
+    /// instructions if one is available. Useful for users on old distro
+    /// packages, who wouldn't otherwise know to upgrade.
+    #[arg(long)]
+    check: bool,
+}
+
+pub fn run(args: Args, _config: Config, _api_key: String) -> Result<(), anyhow::Error> {
+    println!("patisserie {}", CURRENT_VERSION);
+
+    if !args.check {
+        return Ok(());
+    }
+
+    let latest = release::latest_version()?;
+    if release::is_newer(&latest, CURRENT_VERSION)? {
+        println!(
+            "A newer version is available: {} (you have {}).",
+            latest, CURRENT_VERSION
+        );
+        println!("See https://github.com/freshstrangemusic/patisserie/releases/latest to upgrade.");
+    } else {
+        println!("You are running the latest version.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use directories::ProjectDirs;
+use serde::Deserialize;
+
+/// User configuration, loaded from `patisserie/config.toml` in the platform
+/// configuration directory (e.g. `~/.config/patisserie/config.toml` on
+/// Linux).
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// The default number of views a paste can have before expiring, unless
+    /// overridden with `--max-views`.
+    pub max_views: Option<u32>,
+
+    /// Named durations that can be passed to `--duration`, e.g. `sprint =
+    /// "2w"` or `standup = "4h"`.
+    #[serde(default)]
+    pub duration_aliases: HashMap<String, String>,
+
+    /// The default requests-per-minute ceiling for batch operations
+    /// (`purge`, `backup`, `restore`, and `paste` with more than one
+    /// path), unless overridden with `--requests-per-minute`.
+    pub requests_per_minute: Option<u32>,
+
+    /// The default number of seconds to wait for standard input to be read
+    /// in full before aborting, unless overridden with `--stdin-timeout`.
+    pub stdin_timeout_secs: Option<u64>,
+
+    /// Whether to keep a local archive of every upload's content, so it
+    /// outlives the paste's expiry on pastery.net, unless overridden with
+    /// `--archive`.
+    #[serde(default)]
+    pub archive_uploads: bool,
+
+    /// Where to append an audit log entry (one JSON line per operation:
+    /// who, what, when, url, size, and sha256) for `paste`, `get`, and
+    /// `renew`, for compliance reviews. Unset (the default) keeps no log.
+    pub audit_log_path: Option<String>,
+
+    /// A URL to POST a JSON notification to after every successful upload,
+    /// unless overridden with `--webhook`.
+    pub webhook_url: Option<String>,
+
+    /// The incoming webhook URL used to post to Slack when `--notify
+    /// slack:#channel` is given.
+    pub slack_webhook_url: Option<String>,
+
+    /// The homeserver URL used to post to a Matrix room when `--notify
+    /// matrix:!room:server` is given, e.g. `https://matrix.org`.
+    pub matrix_homeserver_url: Option<String>,
+
+    /// The access token used to authenticate with `matrix_homeserver_url`.
+    pub matrix_access_token: Option<String>,
+
+    /// The SMTP server used to send `--email` notifications, e.g.
+    /// `smtp.example.com`. If not set, the local `sendmail` binary is used
+    /// instead.
+    pub smtp_server: Option<String>,
+
+    /// The username used to authenticate with `smtp_server`, if it requires
+    /// authentication.
+    pub smtp_username: Option<String>,
+
+    /// The password used to authenticate with `smtp_server`, if it requires
+    /// authentication.
+    pub smtp_password: Option<String>,
+
+    /// Pins the clipboard mechanism used by `--copy` (`wayland`, `x11`,
+    /// `osc52`, `tmux`, `windows`, or `macos`) instead of detecting one
+    /// automatically, since auto-detection guesses wrong inside nested
+    /// SSH/tmux setups.
+    pub clipboard_backend: Option<String>,
+
+    /// The browser command used by `--open`, e.g. `firefox`, unless
+    /// overridden with `--browser`. Falls back to the `BROWSER` environment
+    /// variable, then the system default browser.
+    pub browser: Option<String>,
+
+    /// User-defined command aliases, e.g. `log = "paste -d 3d --strip-ansi
+    /// --lang text"`, invoked as `patisserie log`.
+    ///
+    /// The alias's value is split into arguments the way a shell would (so
+    /// quoting works as expected) and spliced in place of the alias name;
+    /// anything the alias itself doesn't consume, such as trailing paths,
+    /// is passed straight through.
+    #[serde(default, rename = "alias")]
+    pub aliases: HashMap<String, String>,
+
+    /// A command run before every upload, with the paste's content on its
+    /// standard input. The upload is aborted if it does not exit
+    /// successfully, letting it enforce policy such as blocking secrets.
+    pub pre_upload_hook: Option<String>,
+
+    /// A command run after every successful upload, with the paste's URL
+    /// available as the `PATISSERIE_URL` environment variable.
+    pub post_upload_hook: Option<String>,
+
+    /// A command run after every successful upload, with `{url}`, `{id}`,
+    /// `{title}`, `{language}`, and `{expiry}` placeholders substituted in,
+    /// e.g. `on_success = "xdg-open {url}"`.
+    ///
+    /// Unlike `post_upload_hook`, a failure here is only reported as a
+    /// warning; it does not affect the upload's own exit code.
+    pub on_success: Option<String>,
+
+    /// Per-extension defaults, e.g. `[filetype.log]` (`duration`,
+    /// `strip_ansi`, `max_lines`) or `[filetype.patch]` (`language`),
+    /// applied to a file being uploaded based on its extension.
+    #[serde(default, rename = "filetype")]
+    pub filetypes: HashMap<String, FiletypeConfig>,
+
+    /// Whether to passively check for a newer release at most once a day, so
+    /// users on old distro packages find out they're behind. Off by
+    /// default, since it makes a network request on an otherwise unrelated
+    /// command; see `patisserie version --check` for an on-demand check.
+    #[serde(default)]
+    pub check_for_updates: bool,
+
+    /// Overrides the `User-Agent` header sent with every pastery.net
+    /// request, instead of the default `patisserie/<version>`, for
+    /// corporate proxies that filter on it.
+    pub user_agent: Option<String>,
+
+    /// Forces HTTP/1.1 instead of negotiating HTTP/2, for flaky
+    /// satellite/VPN links where HTTP/2 multiplexing performs worse than
+    /// plain HTTP/1.1 keep-alive.
+    #[serde(default)]
+    pub http1_only: bool,
+
+    /// The TCP keepalive interval, in seconds, for the underlying
+    /// connection pool, for links that silently drop idle connections.
+    pub tcp_keepalive_secs: Option<u64>,
+
+    /// How long, in seconds, a pooled idle connection is kept open before
+    /// being closed.
+    pub pool_idle_timeout_secs: Option<u64>,
+
+    /// Restricts connections to pastery.net to one address family, set from
+    /// `-4`/`-6` rather than the config file.
+    #[serde(skip)]
+    pub ip_version: Option<patisserie::api::IpVersion>,
+
+    /// DNS resolution overrides set from `--resolve HOST:PORT:ADDR` rather
+    /// than the config file.
+    #[serde(skip)]
+    pub resolve_overrides: Vec<(String, SocketAddr)>,
+
+    /// The cassette file to append every API interaction to, set from
+    /// `--record FILE` rather than the config file.
+    #[serde(skip)]
+    pub record_cassette: Option<Utf8PathBuf>,
+
+    /// The already-parsed cassette to replay API interactions from, set
+    /// from `--replay FILE` rather than the config file. `Some` (even if
+    /// empty) means replay mode is active.
+    #[serde(skip)]
+    pub replay_cassette: Option<Vec<patisserie::vcr::Interaction>>,
+}
+
+/// The defaults for one `[filetype.*]` section.
+#[derive(Debug, Default, Deserialize)]
+pub struct FiletypeConfig {
+    /// The default duration for files with this extension, unless
+    /// overridden with `--duration`.
+    pub duration: Option<String>,
+
+    /// The language to use for files with this extension, unless
+    /// overridden with `--lang`.
+    pub language: Option<String>,
+
+    /// Whether to strip ANSI escape codes from files with this extension by
+    /// default, unless `--strip-ansi` is already given.
+    #[serde(default)]
+    pub strip_ansi: bool,
+
+    /// The default `--max-lines` for files with this extension.
+    pub max_lines: Option<usize>,
+}
+
+impl Config {
+    /// Loads the configuration file, or returns the default configuration if
+    /// it does not exist.
+    pub fn load() -> Result<Self, anyhow::Error> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                toml::from_str(&contents).with_context(|| format!("Could not parse `{}'", path))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Could not read config file `{}'", path)),
+        }
+    }
+
+    fn path() -> Option<Utf8PathBuf> {
+        let dirs = ProjectDirs::from("", "", "patisserie")?;
+        Utf8PathBuf::from_path_buf(dirs.config_dir().join("config.toml")).ok()
+    }
+
+    /// The directory `patisserie template` loads named templates from, e.g.
+    /// `~/.config/patisserie/templates` on Linux.
+    pub fn templates_dir() -> Option<Utf8PathBuf> {
+        let dirs = ProjectDirs::from("", "", "patisserie")?;
+        Utf8PathBuf::from_path_buf(dirs.config_dir().join("templates")).ok()
+    }
+
+    /// Builds the connection tuning options used to construct a
+    /// `PasteryClient` from this config's settings.
+    pub fn connection_options(&self) -> patisserie::api::ConnectionOptions<'_> {
+        patisserie::api::ConnectionOptions {
+            user_agent: self.user_agent.as_deref(),
+            http1_only: self.http1_only,
+            tcp_keepalive: self.tcp_keepalive_secs.map(Duration::from_secs),
+            pool_idle_timeout: self.pool_idle_timeout_secs.map(Duration::from_secs),
+            ip_version: self.ip_version,
+            resolve_overrides: &self.resolve_overrides,
+            record: self.record_cassette.as_deref().map(Utf8Path::as_std_path),
+            replay: self.replay_cassette.as_deref(),
+        }
+    }
+}
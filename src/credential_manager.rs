@@ -0,0 +1,32 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Reads the pastery API key from the Windows Credential Manager, used as a
+//! fallback when neither `--api-key` nor `PASTERY_API_KEY` is set, so the
+//! key doesn't have to sit in a plaintext environment variable.
+
+/// Reads the API key stored under the `patisserie` service in the Windows
+/// Credential Manager, e.g. as set with `cmdkey /generic:patisserie /user:PASTERY_API_KEY /pass:...`.
+#[cfg(windows)]
+pub fn get_api_key() -> Result<String, anyhow::Error> {
+    use anyhow::Context;
+
+    keyring::Entry::new("patisserie", "PASTERY_API_KEY")
+        .context("Could not open the Windows Credential Manager")?
+        .get_password()
+        .context("Could not read the API key from the Windows Credential Manager")
+}
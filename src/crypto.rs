@@ -0,0 +1,118 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Passphrase-based encryption for data patisserie keeps at rest (currently
+//! just the local archive), so a stolen disk image doesn't leak paste
+//! content. The key is derived from a passphrase with Argon2 rather than
+//! used directly, and a random salt and nonce are stored alongside the
+//! ciphertext so no state needs to be kept between runs.
+
+use anyhow::anyhow;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, Generate, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// The environment variable holding the passphrase used to encrypt and
+/// decrypt the local archive. If unset, the archive is stored unencrypted.
+pub const PASSPHRASE_ENV_VAR: &str = "PASTERY_ARCHIVE_PASSPHRASE";
+
+/// Reads the archive passphrase from [`PASSPHRASE_ENV_VAR`], if set.
+pub fn passphrase() -> Option<String> {
+    std::env::var(PASSPHRASE_ENV_VAR).ok()
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, anyhow::Error> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| anyhow!("Could not derive encryption key: {}", err))?;
+
+    Ok(key.into())
+}
+
+/// Encrypts `plaintext` with `passphrase`, returning `salt || nonce ||
+/// ciphertext`.
+pub fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    let salt: [u8; SALT_LEN] = Generate::generate();
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(&key);
+    let nonce = XNonce::generate();
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|err| anyhow!("Could not encrypt archive content: {}", err))?;
+
+    let mut out = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypts data previously produced by [`encrypt`] with the same
+/// `passphrase`.
+pub fn decrypt(passphrase: &str, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(anyhow!("Archive file is too short to be valid"));
+    }
+
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt)?;
+    let cipher = XChaCha20Poly1305::new(&key);
+    let nonce =
+        XNonce::try_from(nonce_bytes).map_err(|_| anyhow!("Archive file has a malformed nonce"))?;
+
+    cipher
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| anyhow!("Could not decrypt archive content; wrong passphrase?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_with_the_right_passphrase() {
+        let ciphertext = encrypt("correct horse battery staple", b"secret snippet").unwrap();
+        let plaintext = decrypt("correct horse battery staple", &ciphertext).unwrap();
+        assert_eq!(plaintext, b"secret snippet");
+    }
+
+    #[test]
+    fn fails_with_the_wrong_passphrase() {
+        let ciphertext = encrypt("correct horse battery staple", b"secret snippet").unwrap();
+        assert!(decrypt("wrong passphrase", &ciphertext).is_err());
+    }
+
+    #[test]
+    fn fails_on_truncated_data() {
+        assert!(decrypt("correct horse battery staple", b"too short").is_err());
+    }
+
+    #[test]
+    fn each_encryption_uses_a_fresh_salt_and_nonce() {
+        let first = encrypt("correct horse battery staple", b"secret snippet").unwrap();
+        let second = encrypt("correct horse battery staple", b"secret snippet").unwrap();
+        assert_ne!(first, second);
+    }
+}
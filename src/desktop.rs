@@ -0,0 +1,51 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Desktop notifications for uploads started with `--desktop-notify`, so an
+//! upload left running in the background still surfaces its result.
+//!
+//! A missing or unreachable notification daemon (common on headless
+//! machines) is reported as a warning rather than failing the upload it is
+//! only meant to announce.
+
+use notify_rust::Notification;
+
+/// Shows a desktop notification that the paste at `url` finished uploading.
+pub fn notify_success(url: &str) {
+    show(Notification::new().summary("Paste uploaded").body(url));
+}
+
+/// Shows a desktop notification that an upload failed.
+pub fn notify_failure(err: &anyhow::Error) {
+    show(
+        Notification::new()
+            .summary("Paste upload failed")
+            .body(&err.to_string()),
+    );
+}
+
+fn show(notification: &mut Notification) {
+    if let Err(err) = notification.show() {
+        eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "Warning: could not show desktop notification: {}",
+                err
+            ))
+        );
+    }
+}
@@ -0,0 +1,264 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::anyhow;
+
+pub const ONE_MINUTE: u32 = 1;
+pub const ONE_HOUR: u32 = 60;
+pub const ONE_DAY: u32 = ONE_HOUR * 24;
+pub const ONE_WEEK: u32 = ONE_DAY * 7;
+pub const ONE_MONTH: u32 = ONE_DAY * 30;
+pub const ONE_YEAR: u32 = ONE_DAY * 365;
+pub const ONE_HUNDRED_YEARS: u32 = ONE_YEAR * 100;
+
+/// Parses a single `<amount><unit>` component of a duration, returning the
+/// component's value in minutes and the unparsed remainder of the string.
+fn parse_duration_component<'a>(s: &'a str, whole: &str) -> Result<(f64, &'a str), anyhow::Error> {
+    let digit_end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+
+    if digit_end == 0 {
+        return Err(anyhow!("Expected a number in duration `{}'", whole));
+    }
+
+    let (amount, rest) = s.split_at(digit_end);
+    let amount: f64 = amount
+        .parse()
+        .map_err(|_| anyhow!("Invalid number `{}' in duration `{}'", amount, whole))?;
+
+    let unit_end = rest
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (unit, rest) = rest.split_at(unit_end);
+    let unit = if unit.is_empty() { "m" } else { unit };
+
+    let scale = match unit {
+        "m" => ONE_MINUTE,
+        "h" => ONE_HOUR,
+        "d" => ONE_DAY,
+        "w" => ONE_WEEK,
+        "mo" => ONE_MONTH,
+        "y" => ONE_YEAR,
+        _ => {
+            return Err(anyhow!(
+                "Unknown unit `{}'; expected one of `m', `h', `d', `w', `mo', or `y'",
+                unit
+            ));
+        }
+    };
+
+    Ok((amount * scale as f64, rest))
+}
+
+/// Parses a single `<amount><letter>` component of an ISO 8601 duration,
+/// returning the amount, the unit letter, and the unparsed remainder.
+fn parse_iso8601_component<'a>(
+    s: &'a str,
+    whole: &str,
+) -> Result<(f64, char, &'a str), anyhow::Error> {
+    let digit_end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+
+    if digit_end == 0 {
+        return Err(anyhow!(
+            "Expected a number in ISO 8601 duration `{}'",
+            whole
+        ));
+    }
+
+    let (amount, rest) = s.split_at(digit_end);
+    let amount: f64 = amount.parse().map_err(|_| {
+        anyhow!(
+            "Invalid number `{}' in ISO 8601 duration `{}'",
+            amount,
+            whole
+        )
+    })?;
+
+    let unit = rest
+        .chars()
+        .next()
+        .ok_or_else(|| anyhow!("Expected a unit letter in ISO 8601 duration `{}'", whole))?;
+
+    Ok((amount, unit, &rest[unit.len_utf8()..]))
+}
+
+/// Parses an ISO 8601 duration (e.g. `PT2H30M` or `P3D`), returning its
+/// value in minutes.
+fn parse_iso8601_duration(s: &str, whole: &str) -> Result<f64, anyhow::Error> {
+    let body = &s[1..]; // Strip the leading `P'.
+    let (date_part, time_part) = match body.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (body, None),
+    };
+
+    let mut total = 0.0;
+
+    let mut remaining = date_part;
+    while !remaining.is_empty() {
+        let (amount, unit, rest) = parse_iso8601_component(remaining, whole)?;
+        let scale = match unit {
+            'Y' => ONE_YEAR as f64,
+            'M' => ONE_MONTH as f64,
+            'W' => ONE_WEEK as f64,
+            'D' => ONE_DAY as f64,
+            _ => {
+                return Err(anyhow!(
+                    "Unexpected unit `{}' in ISO 8601 duration `{}'",
+                    unit,
+                    whole
+                ));
+            }
+        };
+        total += amount * scale;
+        remaining = rest;
+    }
+
+    let mut remaining = time_part.unwrap_or("");
+    while !remaining.is_empty() {
+        let (amount, unit, rest) = parse_iso8601_component(remaining, whole)?;
+        let scale = match unit {
+            'H' => ONE_HOUR as f64,
+            'M' => ONE_MINUTE as f64,
+            'S' => 1.0 / 60.0,
+            _ => {
+                return Err(anyhow!(
+                    "Unexpected unit `{}' in ISO 8601 duration `{}'",
+                    unit,
+                    whole
+                ));
+            }
+        };
+        total += amount * scale;
+        remaining = rest;
+    }
+
+    Ok(total)
+}
+
+fn too_long(s: &str) -> anyhow::Error {
+    anyhow!("Duration `{}' is too long; maximum duration is 100y", s)
+}
+
+/// Parses a duration, in minutes, from a string.
+///
+/// A duration is either:
+///
+/// - One or more `<amount><unit>` components concatenated together (e.g.
+///   `1d12h` or `2h30m`), whose values are summed. Each `<amount>` may be
+///   fractional (e.g. `1.5h` or `0.5d`). A bare number with no unit (e.g.
+///   `90`) is treated as a number of minutes.
+/// - An ISO 8601 duration (e.g. `PT2H30M` or `P3D`).
+/// - The keyword `max` or `forever`, meaning the longest duration the
+///   service allows.
+///
+/// The result is rounded to the nearest whole minute.
+pub fn parse_duration(s: &str) -> Result<u32, anyhow::Error> {
+    if s.eq_ignore_ascii_case("max") || s.eq_ignore_ascii_case("forever") {
+        return Ok(ONE_HUNDRED_YEARS);
+    }
+
+    let total = if s.starts_with('P') {
+        parse_iso8601_duration(s, s)?
+    } else {
+        let mut total = 0.0;
+        let mut remaining = s;
+
+        while !remaining.is_empty() {
+            let (minutes, rest) = parse_duration_component(remaining, s)?;
+            total += minutes;
+            remaining = rest;
+        }
+
+        total
+    };
+
+    if total.is_sign_negative() || total > ONE_HUNDRED_YEARS as f64 {
+        Err(too_long(s))
+    } else {
+        Ok(total.round() as u32)
+    }
+}
+
+/// Resolves a duration given on the command line, which may be a raw
+/// duration string or the name of an alias defined in the config file.
+pub fn resolve_duration(
+    raw: &str,
+    aliases: &std::collections::HashMap<String, String>,
+) -> Result<u32, anyhow::Error> {
+    let raw = aliases.get(raw).map_or(raw, String::as_str);
+    parse_duration(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_unit() {
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert_eq!(parse_duration("2h").unwrap(), 2 * ONE_HOUR);
+        assert_eq!(parse_duration("3d").unwrap(), 3 * ONE_DAY);
+    }
+
+    #[test]
+    fn combined_units_are_summed() {
+        assert_eq!(parse_duration("1d12h").unwrap(), ONE_DAY + 12 * ONE_HOUR);
+        assert_eq!(
+            parse_duration("2h30m").unwrap(),
+            2 * ONE_HOUR + 30 * ONE_MINUTE
+        );
+    }
+
+    #[test]
+    fn fractional_units_round_to_the_nearest_minute() {
+        assert_eq!(parse_duration("1.5h").unwrap(), 90);
+        assert_eq!(parse_duration("0.5d").unwrap(), ONE_DAY / 2);
+    }
+
+    #[test]
+    fn iso8601_durations() {
+        assert_eq!(parse_duration("PT2H30M").unwrap(), 2 * ONE_HOUR + 30);
+        assert_eq!(parse_duration("P3D").unwrap(), 3 * ONE_DAY);
+        assert_eq!(parse_duration("P1Y").unwrap(), ONE_YEAR);
+    }
+
+    #[test]
+    fn max_and_forever_keywords() {
+        assert_eq!(parse_duration("max").unwrap(), ONE_HUNDRED_YEARS);
+        assert_eq!(parse_duration("FOREVER").unwrap(), ONE_HUNDRED_YEARS);
+    }
+
+    #[test]
+    fn rejects_unknown_units() {
+        assert!(parse_duration("3x").is_err());
+    }
+
+    #[test]
+    fn rejects_durations_past_the_cap() {
+        assert!(parse_duration("101y").is_err());
+    }
+
+    #[test]
+    fn resolve_duration_expands_aliases() {
+        let aliases = std::collections::HashMap::from([("sprint".to_owned(), "2w".to_owned())]);
+        assert_eq!(resolve_duration("sprint", &aliases).unwrap(), 2 * ONE_WEEK);
+        assert_eq!(resolve_duration("3d", &aliases).unwrap(), 3 * ONE_DAY);
+    }
+}
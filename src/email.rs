@@ -0,0 +1,77 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Emails a paste's link to someone who isn't watching a chat channel,
+//! through a configured SMTP server if one is set, or the local `sendmail`
+//! binary otherwise.
+
+use anyhow::Context;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SendmailTransport, SmtpTransport, Transport};
+
+use crate::config::Config;
+
+const FROM_ADDRESS: &str = "patisserie@localhost";
+
+/// Emails `url` (and `title`, if any) to `to`.
+pub fn send(
+    config: &Config,
+    to: &str,
+    title: Option<&str>,
+    url: &str,
+) -> Result<(), anyhow::Error> {
+    let subject = match title {
+        Some(title) => format!("Paste: {}", title),
+        None => "Paste".to_owned(),
+    };
+
+    let message = Message::builder()
+        .from(
+            FROM_ADDRESS
+                .parse()
+                .context("Could not parse the from address")?,
+        )
+        .to(to
+            .parse()
+            .with_context(|| format!("`{}' is not a valid email address", to))?)
+        .subject(subject)
+        .body(url.to_owned())
+        .context("Could not build email message")?;
+
+    match &config.smtp_server {
+        Some(server) => {
+            let mut builder = SmtpTransport::relay(server)
+                .with_context(|| format!("Could not connect to SMTP server `{}'", server))?;
+            if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password)
+            {
+                builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+            }
+
+            builder
+                .build()
+                .send(&message)
+                .with_context(|| format!("Could not send email to `{}'", to))?;
+        }
+        None => {
+            SendmailTransport::new()
+                .send(&message)
+                .with_context(|| format!("Could not send email to `{}' via sendmail", to))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Process exit codes, so that wrapper scripts can react to specific
+//! failure modes without parsing stderr text.
+//!
+//! Bad-argument failures are handled directly by clap and exit with its own
+//! conventional code (2) before any of these are ever reached.
+
+/// A documented, stable exit code for a specific failure mode.
+#[derive(Debug, Clone, Copy)]
+#[repr(i32)]
+pub enum ExitCode {
+    /// The command completed successfully.
+    Success = 0,
+    /// No API key was available, from `--api-key` or `PASTERY_API_KEY`.
+    AuthFailure = 3,
+    /// The HTTP request to pastery.net could not be made at all.
+    NetworkFailure = 4,
+    /// Pastery.net returned a well-formed error response.
+    ApiError = 5,
+    /// Local input (a file, standard input, or the config file) could not
+    /// be read or was invalid.
+    InputError = 6,
+}
+
+impl ExitCode {
+    pub fn as_i32(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Classifies an error returned from a subcommand into one of the exit
+/// codes above, by looking for a recognizable cause in its source chain.
+pub fn classify(err: &anyhow::Error) -> ExitCode {
+    for cause in err.chain() {
+        if let Some(api_err) = cause.downcast_ref::<patisserie::api::Error>() {
+            return match api_err {
+                patisserie::api::Error::Auth => ExitCode::AuthFailure,
+                patisserie::api::Error::Network(_) => ExitCode::NetworkFailure,
+                _ => ExitCode::ApiError,
+            };
+        }
+    }
+
+    if err.chain().any(|cause| cause.is::<std::io::Error>()) {
+        ExitCode::InputError
+    } else {
+        ExitCode::ApiError
+    }
+}
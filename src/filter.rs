@@ -0,0 +1,64 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Piping a paste's content through an arbitrary shell pipeline with
+//! `--filter`, e.g. `--filter 'grep -v password | tail -n 500'`, before
+//! upload.
+
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+
+use anyhow::{Context, bail};
+
+use crate::shell;
+
+/// Runs `content` through `pipeline` (a shell command, which may itself
+/// contain pipes) and returns what it writes to standard output.
+///
+/// Writes to the child's stdin on a separate thread while the main thread
+/// waits on its output, since a filter that produces output before fully
+/// consuming its input (e.g. `cat` or `tail`) would otherwise deadlock: the
+/// child blocks writing to a full stdout pipe while we block writing to its
+/// stdin, and neither side is left to drain the other.
+pub fn apply(pipeline: &str, content: &str) -> Result<String, anyhow::Error> {
+    let mut child = shell::command(pipeline)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run --filter `{}'", pipeline))?;
+
+    let mut stdin = child.stdin.take().expect("stdin was piped");
+    let content = content.to_owned();
+    let writer = thread::spawn(move || stdin.write_all(content.as_bytes()));
+
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Could not wait for --filter `{}'", pipeline))?;
+
+    writer
+        .join()
+        .expect("the stdin-writing thread should not panic")
+        .with_context(|| format!("Could not write to --filter `{}'", pipeline))?;
+
+    if !output.status.success() {
+        bail!("--filter `{}' exited with {}", pipeline, output.status);
+    }
+
+    String::from_utf8(output.stdout)
+        .with_context(|| format!("--filter `{}' did not produce valid UTF-8", pipeline))
+}
@@ -0,0 +1,84 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
+/// Renders `body` as ANSI-colored text, guessing the syntect syntax from
+/// pastery's `language` tag.
+///
+/// Falls back to plain, uncolored text if no syntax matches `language`.
+pub fn highlight(body: &str, language: &str) -> String {
+    let syntaxes = SyntaxSet::load_defaults_newlines();
+    let themes = ThemeSet::load_defaults();
+
+    let Some(syntax) = syntaxes
+        .find_syntax_by_token(language)
+        .or_else(|| syntaxes.find_syntax_by_extension(language))
+    else {
+        return body.to_owned();
+    };
+
+    let theme = &themes.themes["base16-ocean.dark"];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut rendered = String::new();
+    for line in body.lines() {
+        let Ok(ranges) = highlighter.highlight_line(line, &syntaxes) else {
+            return body.to_owned();
+        };
+        rendered.push_str(&as_24_bit_terminal_escaped(&ranges[..], false));
+        rendered.push_str("\x1b[0m\n");
+    }
+
+    rendered
+}
+
+/// Writes `content` to `$PAGER` (falling back to `less -R`), or to standard
+/// output if spawning the pager fails.
+pub fn page(content: &str) -> Result<(), anyhow::Error> {
+    use std::io::Write;
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_owned());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(content.as_bytes());
+            }
+            child.wait().context("Could not wait for pager to exit")?;
+        }
+        Err(_) => print!("{}", content),
+    }
+
+    Ok(())
+}
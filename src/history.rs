@@ -0,0 +1,281 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A local record of uploaded pastes, keyed by content hash, so that a retry
+//! after an ambiguous failure can recognize a paste that actually made it to
+//! pastery.net instead of creating a duplicate.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use anyhow::{Context, anyhow};
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+/// A single successful upload, recorded after the fact.
+#[derive(Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub hash: String,
+    pub id: String,
+    pub url: String,
+
+    /// Tags passed with `--tag` at paste time, so a group of related pastes
+    /// (e.g. everything uploaded during an incident) can be found together.
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// A free-form note attached after the fact with `history annotate`, to
+    /// capture context the paste itself doesn't, e.g. who it was sent to.
+    pub note: Option<String>,
+
+    /// The paste's title at upload time, if any, so commands like `share`
+    /// don't need to re-fetch it from pastery.net.
+    pub title: Option<String>,
+
+    /// The RFC 3339 timestamp at which the paste is expected to expire, so
+    /// commands like `share` can report how much time is left.
+    pub expires_at: Option<String>,
+}
+
+/// Hashes paste content so it can be looked up in the history regardless of
+/// the title, language, or duration it was uploaded with.
+pub fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    format!("{:x}", digest)
+}
+
+/// Computes the RFC 3339 timestamp at which a paste with the given
+/// duration (in minutes) will expire, for storage in a [`HistoryEntry`].
+pub fn expiry_timestamp(duration_minutes: u32) -> String {
+    let expiry = OffsetDateTime::now_utc() + Duration::minutes(i64::from(duration_minutes));
+    expiry
+        .format(&Rfc3339)
+        .expect("a paste's expiry is always representable as RFC 3339")
+}
+
+fn path() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("history.jsonl")).ok()
+}
+
+/// Appends `entry` to the history, creating the data directory if needed.
+pub fn record(entry: &HistoryEntry) -> Result<(), anyhow::Error> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create directory `{}'", dir))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Could not open file `{}' for writing", path))?;
+
+    let line = serde_json::to_string(entry).context("Could not serialize history entry")?;
+    writeln!(file, "{}", line).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
+
+/// Finds the most recent history entry for `hash`, if any.
+pub fn find_by_hash(hash: &str) -> Result<Option<HistoryEntry>, anyhow::Error> {
+    Ok(list()?.into_iter().rfind(|entry| entry.hash == hash))
+}
+
+/// Resolves a shorthand reference to a history entry: `last` or a bare
+/// number (optionally prefixed with `~`) for the Nth most recent entry
+/// (`last`, `1`, and `~1` all mean the most recent one), falling back to
+/// the id of a specific paste otherwise.
+pub fn resolve(reference: &str) -> Result<Option<HistoryEntry>, anyhow::Error> {
+    let mut entries = list()?;
+    entries.reverse();
+
+    let ordinal = if reference == "last" {
+        Some(1)
+    } else {
+        reference
+            .strip_prefix('~')
+            .unwrap_or(reference)
+            .parse::<usize>()
+            .ok()
+    };
+
+    if let Some(n) = ordinal {
+        return Ok(n
+            .checked_sub(1)
+            .and_then(|index| entries.into_iter().nth(index)));
+    }
+
+    Ok(entries
+        .into_iter()
+        .find(|entry| entry.id == reference || entry.url == reference))
+}
+
+/// Whether `reference` is shorthand for a history entry (`last`, a bare
+/// number, or a `~`-prefixed number) rather than a literal id or URL.
+fn is_shorthand(reference: &str) -> bool {
+    reference == "last"
+        || reference
+            .strip_prefix('~')
+            .unwrap_or(reference)
+            .parse::<usize>()
+            .is_ok()
+}
+
+/// Resolves `reference` to a paste id if it is shorthand for a history
+/// entry (`last`, `~N`, or a bare `N` for the Nth most recent upload),
+/// leaving any other reference (a literal id or URL) untouched, so callers
+/// can pass it straight to [`patisserie::api::extract_id`].
+pub fn resolve_id(reference: &str) -> Result<String, anyhow::Error> {
+    if !is_shorthand(reference) {
+        return Ok(reference.to_owned());
+    }
+
+    let entry =
+        resolve(reference)?.ok_or_else(|| anyhow!("No history entry found for `{}'", reference))?;
+    Ok(entry.id)
+}
+
+/// Sets `note` on the most recent history entry for `id`, rewriting the
+/// history file in place.
+pub fn annotate(id: &str, note: &str) -> Result<(), anyhow::Error> {
+    let path = path().context("Could not determine a data directory for history")?;
+
+    let mut entries = list()?;
+    let entry = entries
+        .iter_mut()
+        .rev()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| anyhow!("No history entry found for paste `{}'", id))?;
+    entry.note = Some(note.to_owned());
+
+    let mut contents = String::new();
+    for entry in &entries {
+        let line = serde_json::to_string(entry).context("Could not serialize history entry")?;
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+
+    fs::write(&path, contents).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
+
+/// Rewrites the history file keeping only the entries for which `keep`
+/// returns `true`, for opportunistic pruning (see [`crate::prune`]).
+pub fn retain(keep: impl Fn(&HistoryEntry) -> bool) -> Result<(), anyhow::Error> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+
+    let mut entries = list()?;
+    let original_len = entries.len();
+    entries.retain(keep);
+
+    if entries.len() == original_len {
+        return Ok(());
+    }
+
+    let mut contents = String::new();
+    for entry in &entries {
+        let line = serde_json::to_string(entry).context("Could not serialize history entry")?;
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+
+    fs::write(&path, contents).with_context(|| format!("Could not write to file `{}'", path))?;
+
+    Ok(())
+}
+
+/// Lists every history entry, oldest first.
+pub fn list() -> Result<Vec<HistoryEntry>, anyhow::Error> {
+    let Some(path) = path() else {
+        return Ok(Vec::new());
+    };
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Could not read file `{}'", path)),
+    };
+
+    contents
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Could not parse history entry `{}'", line))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod content_hash_tests {
+    use super::*;
+
+    #[test]
+    fn same_content_hashes_the_same() {
+        assert_eq!(content_hash("hello world"), content_hash("hello world"));
+    }
+
+    #[test]
+    fn different_content_hashes_differently() {
+        assert_ne!(content_hash("hello world"), content_hash("goodbye world"));
+    }
+
+    #[test]
+    fn is_a_lowercase_hex_sha256_digest() {
+        let hash = content_hash("hello world");
+        assert_eq!(hash.len(), 64);
+        assert!(
+            hash.chars()
+                .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+        );
+    }
+}
+
+#[cfg(test)]
+mod shorthand_tests {
+    use super::*;
+
+    #[test]
+    fn last_and_bare_ordinals_are_shorthand() {
+        assert!(is_shorthand("last"));
+        assert!(is_shorthand("1"));
+        assert!(is_shorthand("2"));
+    }
+
+    #[test]
+    fn tilde_prefixed_ordinals_are_shorthand() {
+        assert!(is_shorthand("~1"));
+        assert!(is_shorthand("~2"));
+    }
+
+    #[test]
+    fn ids_and_urls_are_not_shorthand() {
+        assert!(!is_shorthand("abc123"));
+        assert!(!is_shorthand("https://www.pastery.net/abc123/"));
+        assert!(!is_shorthand("~abc"));
+    }
+}
@@ -0,0 +1,69 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Config-defined pre- and post-upload hook commands, for custom policy and
+//! integrations without having to fork patisserie.
+//!
+//! Both hooks are run through a shell, so pipelines and redirections in the
+//! configured command work as expected.
+
+use std::io::Write;
+use std::process::Stdio;
+
+use anyhow::{Context, bail};
+
+use crate::shell;
+
+/// Runs `command` with `content` on its standard input, failing the upload
+/// if it does not exit successfully.
+pub fn run_pre_upload(command: &str, content: &[u8]) -> Result<(), anyhow::Error> {
+    let mut child = shell::command(command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run pre-upload hook `{}'", command))?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin was piped")
+        .write_all(content)
+        .with_context(|| format!("Could not write to pre-upload hook `{}'", command))?;
+
+    let status = child
+        .wait()
+        .with_context(|| format!("Could not wait for pre-upload hook `{}'", command))?;
+
+    if !status.success() {
+        bail!("Pre-upload hook `{}' exited with {}", command, status);
+    }
+
+    Ok(())
+}
+
+/// Runs `command` with the paste's URL available as `$PATISSERIE_URL`.
+pub fn run_post_upload(command: &str, url: &str) -> Result<(), anyhow::Error> {
+    let status = shell::command(command)
+        .env("PATISSERIE_URL", url)
+        .status()
+        .with_context(|| format!("Could not run post-upload hook `{}'", command))?;
+
+    if !status.success() {
+        bail!("Post-upload hook `{}' exited with {}", command, status);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Printing diagnostics (warnings, errors, and verbose info) for
+//! `--log-format`, so a CI/k8s job can ask for one JSON object per line on
+//! standard error instead of free-form text.
+
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+use serde::Serialize;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::color;
+
+/// The format diagnostics are printed in, as given to `--log-format`.
+#[derive(Clone, Copy, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// Parses a `--log-format` value.
+    pub fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!("Expected one of `text' or `json', got `{}'", s)),
+        }
+    }
+}
+
+static FORMAT: OnceLock<LogFormat> = OnceLock::new();
+
+/// Remembers `format` for the `warn`/`error`/`info` helpers used throughout
+/// the program. Should be called once, early in `main`.
+pub fn init(format: LogFormat) {
+    let _ = FORMAT.set(format);
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    level: &'a str,
+    message: &'a str,
+    timestamp: String,
+}
+
+fn print_json(level: &str, message: &str) {
+    let record = Record {
+        level,
+        message,
+        timestamp: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .expect("the current time is always representable as RFC 3339"),
+    };
+    eprintln!(
+        "{}",
+        serde_json::to_string(&record).expect("a Record always serializes")
+    );
+}
+
+/// Prints a warning diagnostic, e.g. that pruning old history failed.
+pub fn warn(message: &str) {
+    match FORMAT.get().copied().unwrap_or_default() {
+        LogFormat::Text => eprintln!("{}", color::warning(&format!("Warning: {}", message))),
+        LogFormat::Json => print_json("warn", message),
+    }
+}
+
+/// Prints a fatal error diagnostic, with its full causal chain.
+pub fn error(err: &anyhow::Error) {
+    let message = format!("{:?}", err);
+    match FORMAT.get().copied().unwrap_or_default() {
+        LogFormat::Text => eprintln!("{}", color::error(&format!("Error: {}", message))),
+        LogFormat::Json => print_json("error", &message),
+    }
+}
+
+/// Prints an informational diagnostic, e.g. the remaining rate-limit quota.
+pub fn info(message: &str) {
+    match FORMAT.get().copied().unwrap_or_default() {
+        LogFormat::Text => eprintln!("{}", message),
+        LogFormat::Json => print_json("info", message),
+    }
+}
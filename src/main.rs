@@ -15,204 +15,385 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+mod anonymize;
+mod ansi;
+mod archive;
+mod audit;
+mod browser;
+mod clipboard;
+mod collection;
+mod color;
+mod commands;
+mod config;
+mod credential_manager;
+mod crypto;
+mod desktop;
+mod duration;
+mod email;
+mod exit_code;
+mod filter;
+mod highlight;
+mod history;
+mod hooks;
+mod i18n;
 mod language;
-
+mod log;
+mod matrix;
+mod pager;
+mod porcelain;
+mod prune;
+mod queue;
+mod release;
+mod shell;
+mod slack;
+mod spinner;
+mod throttle;
+mod timings;
+mod webhook;
+
+use std::collections::HashMap;
 use std::env;
-use std::fs::File;
-use std::io::{Read, stdin};
+use std::net::{IpAddr, SocketAddr};
 
 use anyhow::{Context, anyhow};
-use camino::{Utf8Path, Utf8PathBuf};
-use clap::Parser;
-use reqwest::Url;
-use reqwest::blocking::Client;
-use serde::Deserialize;
+use camino::Utf8PathBuf;
+use clap::{Parser, Subcommand};
 
-use crate::language::{guess_language, parse_language};
+use crate::color::ColorChoice;
+use crate::config::Config;
+use crate::exit_code::ExitCode;
+use crate::log::LogFormat;
 
-const API_URL: &str = "https://www.pastery.net/api/paste/";
 const API_KEY_ENV_VAR: &str = "PASTERY_API_KEY";
 
 #[derive(Parser)]
+#[command(name = "patisserie")]
 /// A CLI for https://www.pastery.net, the sweetest pastebin in the world.
-struct Options {
+///
+/// An argument of the form `@file` is replaced with the whitespace-separated
+/// arguments read from `file`, one per line, which is useful when invoking
+/// patisserie from a build system with a very long or generated argument
+/// list.
+struct Cli {
     /// Your pastery API key.
     ///
     /// If not provided, it will be read from the PASTERY_API_KEY environment
     /// variable.
     ///
     /// You can find this at https://www.pastery.net/account/.
-    #[arg(long = "api-key")]
+    #[arg(long = "api-key", global = true)]
     api_key: Option<String>,
 
-    /// The duration that this paste will live for.
+    /// Print extra diagnostic information, such as the remaining rate-limit
+    /// quota, to standard error.
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Colorize success, error, and warning output.
     ///
-    /// After this time, the paste will be deleted.
+    /// `auto` (the default) colorizes when the relevant stream is a
+    /// terminal and the `NO_COLOR` environment variable is not set.
+    #[arg(
+        long,
+        global = true,
+        value_parser = ColorChoice::parse,
+        default_value = "auto"
+    )]
+    color: ColorChoice,
+
+    /// The format diagnostics (warnings, errors, and `--verbose` info) are
+    /// printed in on standard error.
     ///
-    /// You can specify a period of minutes or a value followed by one of the following units:
-    /// m(inute), h(our), d(ay), mo(nth), y(ear)
-    #[arg(short, long = "duration", default_value = "1d", value_parser = parse_duration)]
-    duration: u32,
+    /// `json` prints one JSON object per line instead of free-form text, so
+    /// a CI/k8s job can feed patisserie's diagnostics into a log pipeline.
+    #[arg(
+        long,
+        global = true,
+        value_parser = LogFormat::parse,
+        default_value = "text"
+    )]
+    log_format: LogFormat,
+
+    /// Force IPv4 for all connections to pastery.net, for sites where
+    /// broken IPv6 makes every request hang for seconds before falling
+    /// back.
+    #[arg(short = '4', global = true, conflicts_with = "ipv6")]
+    ipv4: bool,
+
+    /// Force IPv6 for all connections to pastery.net.
+    #[arg(short = '6', global = true)]
+    ipv6: bool,
+
+    /// Override DNS resolution for `host:port` to a specific IP address, as
+    /// `host:port:addr`, for testing against a staging deployment or
+    /// working around split DNS. May be given multiple times.
+    #[arg(long = "resolve", global = true, value_name = "HOST:PORT:ADDR")]
+    resolve: Vec<String>,
+
+    /// Record every pastery.net API interaction made during this invocation
+    /// to a cassette file, for building a `--replay` cassette to test
+    /// patisserie itself, or scripts that wrap it, without network access.
+    #[arg(long, global = true, value_name = "FILE", conflicts_with = "replay")]
+    record: Option<Utf8PathBuf>,
+
+    /// Replay API interactions from a cassette previously written by
+    /// `--record FILE` instead of making real requests.
+    #[arg(long, global = true, value_name = "FILE")]
+    replay: Option<Utf8PathBuf>,
+
+    /// Never page long output through $PAGER, even when standard output is
+    /// a terminal.
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
 
-    /// The language for the paste.
-    ///
-    /// If not provided, patisserie will attempt to guess based on the file
-    /// extension. You can use the special value "autodetect" to have pastery
-    /// detect the language.
-    #[arg(short, long = "lang", value_parser = parse_language)]
-    language: Option<&'static str>,
+#[derive(Subcommand)]
+enum Command {
+    Paste(Box<commands::paste::Args>),
+    Purge(commands::purge::Args),
+    Renew(commands::renew::Args),
+    Clone(commands::clone::Args),
+    Verify(commands::verify::Args),
+    Get(commands::get::Args),
+    Cat(commands::cat::Args),
+    Backup(commands::backup::Args),
+    Restore(commands::restore::Args),
+    Limits(commands::limits::Args),
+    #[command(alias = "retry")]
+    Flush(commands::flush::Args),
+    History(commands::history::Args),
+    Share(commands::share::Args),
+    Expiring(commands::expiring::Args),
+    Ping(commands::ping::Args),
+    Version(commands::version::Args),
+    #[command(name = "from-url")]
+    FromUrl(commands::from_url::Args),
+    Edit(commands::edit::Args),
+    Diff(commands::diff::Args),
+    Series(commands::series::Args),
+    Template(commands::template::Args),
+    Collection(commands::collection::Args),
+    Search(commands::search::Args),
+    Pick(commands::pick::Args),
+    Open(commands::open::Args),
+    #[command(name = "copy-url")]
+    CopyUrl(commands::copy_url::Args),
+}
 
-    /// The title of the paste.
-    ///
-    /// If not provided, the name of the file will be used instead.
-    #[arg(short, long)]
-    title: Option<String>,
+/// Expands `@file` arguments into the flags they contain, one per line, so
+/// very long or generated argument lists can be kept out of the shell's own
+/// command-line length limits.
+fn expand_argfiles() -> Result<Vec<String>, anyhow::Error> {
+    argfile::expand_args(argfile::parse_fromfile, argfile::PREFIX)
+        .context("Could not read an @argfile")?
+        .into_iter()
+        .map(|arg| {
+            arg.into_string()
+                .map_err(|arg| anyhow!("Argument `{}' is not valid UTF-8", arg.to_string_lossy()))
+        })
+        .collect()
+}
 
-    /// The number of times the paste can be viewed before expiring.
-    ///
-    /// If not provided, the paste will not have view-based expiration.
-    #[arg(long)]
-    max_views: Option<u32>,
+/// Expands `args[1]` in place if it names an alias defined in the config
+/// file's `[alias]` section, splicing in the alias's own arguments the way a
+/// shell would split them, so anything the alias doesn't consume (such as
+/// trailing paths) is still passed through.
+fn args_with_aliases_expanded(
+    mut args: Vec<String>,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<String>, anyhow::Error> {
+    let Some(alias) = args.get(1).and_then(|arg| aliases.get(arg)) else {
+        return Ok(args);
+    };
 
-    /// The path of the file to upload.
-    ///
-    /// If not provided, the file will be read from standard input.
-    path: Option<Utf8PathBuf>,
+    let expansion = shell_words::split(alias)
+        .with_context(|| format!("Could not parse alias `{}' as a shell command", alias))?;
+    args.splice(1..=1, expansion);
+
+    Ok(args)
 }
 
-fn parse_duration(s: &str) -> Result<u32, anyhow::Error> {
-    const ONE_MINUTE: u32 = 1;
-    const ONE_HOUR: u32 = 60;
-    const ONE_DAY: u32 = ONE_HOUR * 24;
-    const ONE_WEEK: u32 = ONE_DAY * 7;
-    const ONE_MONTH: u32 = ONE_DAY * 30;
-    const ONE_YEAR: u32 = ONE_DAY * 365;
-    const ONE_HUNDRED_YEARS: u32 = ONE_YEAR * 100;
-
-    fn too_long(s: &str) -> anyhow::Error {
-        anyhow!("Duration `{}' is too long; maximum duration is 100y", s)
+/// Inserts the implicit `paste` subcommand when none was given explicitly,
+/// so that `patisserie file.txt` keeps working the way it always has.
+fn args_with_implicit_subcommand(mut args: Vec<String>) -> Vec<String> {
+    let has_explicit_subcommand = args
+        .get(1)
+        .is_some_and(|arg| commands::NAMES.contains(&arg.as_str()));
+    let is_help_or_version = args
+        .get(1)
+        .is_some_and(|arg| matches!(arg.as_str(), "-h" | "--help" | "-V" | "--version"));
+
+    if !has_explicit_subcommand && !is_help_or_version {
+        args.insert(1, "paste".to_owned());
     }
 
-    let (amount, unit) = s
-        .find(|c: char| !c.is_ascii_digit())
-        .map(|idx| s.split_at(idx))
-        .unwrap_or_else(|| (s, "m"));
-
-    let amount: u32 = amount.parse().expect("amount is entirely ascii digits");
-
-    let scale = match unit {
-        "m" => ONE_MINUTE,
-        "h" => ONE_HOUR,
-        "d" => ONE_DAY,
-        "w" => ONE_WEEK,
-        "mo" => ONE_MONTH,
-        "y" => ONE_YEAR,
-        _ => {
-            return Err(anyhow!(
-                "Unknown unit `{}'; expected one of `m', `h', `d', `w', `mo', or `y'",
-                unit
-            ));
-        }
+    args
+}
+
+/// Parses a curl-style `host:port:addr` override for `--resolve`.
+fn parse_resolve_override(raw: &str) -> Result<(String, SocketAddr), anyhow::Error> {
+    let invalid = || {
+        anyhow!(
+            "`--resolve` value `{}' is not of the form HOST:PORT:ADDR",
+            raw
+        )
     };
 
-    amount
-        .checked_mul(scale)
-        .ok_or_else(|| too_long(s))
-        .and_then(|t| {
-            if t > ONE_HUNDRED_YEARS {
-                Err(too_long(s))
-            } else {
-                Ok(t)
-            }
-        })
+    let mut parts = raw.splitn(3, ':');
+    let host = parts
+        .next()
+        .filter(|host| !host.is_empty())
+        .ok_or_else(invalid)?;
+    let port = parts.next().ok_or_else(invalid)?;
+    let addr = parts.next().ok_or_else(invalid)?;
+
+    let port: u16 = port
+        .parse()
+        .with_context(|| format!("`--resolve` value `{}' has an invalid port", raw))?;
+    let addr: IpAddr = addr
+        .parse()
+        .with_context(|| format!("`--resolve` value `{}' has an invalid address", raw))?;
+
+    Ok((host.to_owned(), SocketAddr::new(addr, port)))
 }
 
-#[derive(Deserialize)]
-#[serde(untagged)]
-enum Response {
-    Paste { url: String },
-    Error { error_msg: String },
+/// Reads and parses the cassette at `path`, for `--replay`.
+fn read_cassette(path: &Utf8PathBuf) -> Result<Vec<patisserie::vcr::Interaction>, anyhow::Error> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read cassette `{}'", path))?;
+    patisserie::vcr::parse_cassette(&contents)
+        .with_context(|| format!("Could not parse cassette `{}'", path))
 }
 
-impl Response {
-    fn into_result(self) -> Result<String, anyhow::Error> {
-        match self {
-            Self::Paste { url } => Ok(url),
-            Self::Error { error_msg } => Err(anyhow!(error_msg)),
-        }
+/// Resolves the API key from `--api-key`, the `PASTERY_API_KEY` environment
+/// variable, or (on Windows) the Windows Credential Manager, in that order.
+///
+/// We do not use the `env` feature of clap because it will print the value
+/// of environment variables in help text.
+fn resolve_api_key(cli_api_key: Option<String>) -> Result<String, anyhow::Error> {
+    if let Some(api_key) = cli_api_key {
+        return Ok(api_key);
+    }
+
+    if let Ok(api_key) = env::var(API_KEY_ENV_VAR) {
+        return Ok(api_key);
     }
-}
 
-fn main() -> Result<(), anyhow::Error> {
-    let options = Options::parse();
-
-    // We do not use the `env` feature of clap because it will print the value of
-    // environment variables in help text.
-    let api_key = options
-        .api_key
-        .map_or_else(|| env::var(API_KEY_ENV_VAR), Ok)
-        .with_context(|| {
-            format!(
-                "You must specify either --api-key or set the {} environment variable",
-                API_KEY_ENV_VAR
-            )
-        })?;
-
-    let mut buffer = String::new();
-    if let Some(path) = &options.path {
-        File::open(path)
-            .with_context(|| format!("Could not open file `{}' for reading", path))?
-            .read_to_string(&mut buffer)
-            .with_context(|| format!("Could not read file `{}'", path))?;
-    } else {
-        stdin()
-            .read_to_string(&mut buffer)
-            .context("Could not read from stdin")?;
+    #[cfg(windows)]
+    if let Ok(api_key) = credential_manager::get_api_key() {
+        return Ok(api_key);
     }
 
-    let language = options
-        .language
-        .or_else(|| options.path.as_deref().and_then(guess_language))
-        .unwrap_or("autodetect");
+    let mut args = fluent::FluentArgs::new();
+    args.set("env_var", API_KEY_ENV_VAR);
+    Err(anyhow!(i18n::message("missing-api-key", &args)))
+}
+
+fn main() {
+    std::process::exit(run().as_i32());
+}
+
+fn run() -> ExitCode {
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => return report(err, ExitCode::InputError),
+    };
 
-    let title = options.title.or_else(|| {
-        options
-            .path
-            .as_deref()
-            .and_then(Utf8Path::file_name)
-            .map(ToOwned::to_owned)
-    });
+    if config.check_for_updates {
+        release::maybe_check_passively();
+    }
 
-    let mut url = Url::parse(API_URL).unwrap();
+    let args = match expand_argfiles()
+        .and_then(|args| args_with_aliases_expanded(args, &config.aliases))
     {
-        let mut query = url.query_pairs_mut();
+        Ok(args) => args,
+        Err(err) => return report(err, ExitCode::InputError),
+    };
 
-        query
-            .append_pair("api_key", &api_key)
-            .append_pair("duration", &options.duration.to_string())
-            .append_pair("language", language);
+    let cli = Cli::parse_from(args_with_implicit_subcommand(args));
+    color::init(cli.color);
+    log::init(cli.log_format);
+    pager::init(cli.no_pager);
 
-        if let Some(title) = title {
-            query.append_pair("title", &title);
-        }
+    if let Err(err) = prune::maybe_prune() {
+        log::warn(&format!("could not prune local history: {}", err));
+    }
+
+    if cli.ipv4 {
+        config.ip_version = Some(patisserie::api::IpVersion::V4);
+    } else if cli.ipv6 {
+        config.ip_version = Some(patisserie::api::IpVersion::V6);
+    }
+
+    config.resolve_overrides = match cli
+        .resolve
+        .iter()
+        .map(|raw| parse_resolve_override(raw))
+        .collect()
+    {
+        Ok(overrides) => overrides,
+        Err(err) => return report(err, ExitCode::InputError),
+    };
+
+    config.record_cassette = cli.record;
 
-        if let Some(max_views) = options.max_views {
-            query.append_pair("max_views", &max_views.to_string());
+    if let Some(path) = cli.replay {
+        match read_cassette(&path) {
+            Ok(interactions) => config.replay_cassette = Some(interactions),
+            Err(err) => return report(err, ExitCode::InputError),
         }
     }
 
-    let client = Client::new();
+    let api_key = match resolve_api_key(cli.api_key) {
+        Ok(api_key) => api_key,
+        Err(err) => return report(err, ExitCode::AuthFailure),
+    };
 
-    let paste_url = client
-        .post(url)
-        .body(buffer)
-        .send()
-        .context("Could not make HTTP request")?
-        .json::<Response>()
-        .context("Could not parse JSON response")?
-        .into_result()?;
+    let verbose = cli.verbose;
+    let result = match cli.command {
+        Command::Paste(args) => commands::paste::run(*args, config, api_key, verbose),
+        Command::Purge(args) => commands::purge::run(args, config, api_key, verbose),
+        Command::Renew(args) => commands::renew::run(args, config, api_key, verbose),
+        Command::Clone(args) => commands::clone::run(args, config, api_key, verbose),
+        Command::Verify(args) => commands::verify::run(args, config, api_key, verbose),
+        Command::Get(args) => commands::get::run(args, config, api_key, verbose),
+        Command::Cat(args) => commands::cat::run(args, config, api_key, verbose),
+        Command::Backup(args) => commands::backup::run(args, config, api_key, verbose),
+        Command::Restore(args) => commands::restore::run(args, config, api_key, verbose),
+        Command::Limits(args) => commands::limits::run(args, config, api_key),
+        Command::Flush(args) => commands::flush::run(args, config, api_key, verbose),
+        Command::History(args) => commands::history::run(args, config, api_key),
+        Command::Share(args) => commands::share::run(args, config, api_key),
+        Command::Expiring(args) => commands::expiring::run(args, config, api_key),
+        Command::Ping(args) => commands::ping::run(args, config, api_key),
+        Command::Version(args) => commands::version::run(args, config, api_key),
+        Command::FromUrl(args) => commands::from_url::run(args, config, api_key, verbose),
+        Command::Edit(args) => commands::edit::run(args, config, api_key, verbose),
+        Command::Diff(args) => commands::diff::run(args, config, api_key, verbose),
+        Command::Series(args) => commands::series::run(args, config, api_key, verbose),
+        Command::Template(args) => commands::template::run(args, config, api_key, verbose),
+        Command::Collection(args) => commands::collection::run(args, config, api_key),
+        Command::Search(args) => commands::search::run(args, config),
+        Command::Pick(args) => commands::pick::run(args, config, api_key, verbose),
+        Command::Open(args) => commands::open::run(args, config),
+        Command::CopyUrl(args) => commands::copy_url::run(args, config),
+    };
 
-    println!("{}", paste_url);
+    match result {
+        Ok(()) => ExitCode::Success,
+        Err(err) => {
+            let code = exit_code::classify(&err);
+            report(err, code)
+        }
+    }
+}
 
-    Ok(())
+/// Prints `err` to standard error and returns `code`, for use as the tail
+/// expression of an error-handling branch in [`run`].
+fn report(err: anyhow::Error, code: ExitCode) -> ExitCode {
+    log::error(&err);
+    code
 }
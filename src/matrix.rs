@@ -0,0 +1,74 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Posts a paste's link to a Matrix room via the Client-Server API, for
+//! teams on Matrix/Element instead of Slack.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, anyhow};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct MessageEvent<'a> {
+    msgtype: &'a str,
+    body: &'a str,
+}
+
+/// Sends `text` as an `m.room.message` event to `room_id` on
+/// `homeserver_url`, authenticating with `access_token`.
+pub fn notify(
+    homeserver_url: &str,
+    access_token: &str,
+    room_id: &str,
+    text: &str,
+) -> Result<(), anyhow::Error> {
+    // Each event needs a client-generated transaction id, unique per
+    // access token; the current time in nanoseconds is unique enough for a
+    // short-lived CLI invocation that sends at most one message per room.
+    let txn_id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+
+    let mut url = reqwest::Url::parse(homeserver_url)
+        .with_context(|| format!("`{}' is not a valid Matrix homeserver URL", homeserver_url))?;
+    url.path_segments_mut()
+        .map_err(|()| anyhow!("`{}' is not a valid Matrix homeserver URL", homeserver_url))?
+        .extend([
+            "_matrix",
+            "client",
+            "v3",
+            "rooms",
+            room_id,
+            "send",
+            "m.room.message",
+            &txn_id.to_string(),
+        ]);
+
+    reqwest::blocking::Client::new()
+        .put(url)
+        .bearer_auth(access_token)
+        .json(&MessageEvent {
+            msgtype: "m.text",
+            body: text,
+        })
+        .send()
+        .with_context(|| format!("Could not notify Matrix room `{}'", room_id))?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Paging long human-facing output through `$PAGER`, for `--no-pager`.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use crate::highlight;
+
+static DISABLED: OnceLock<bool> = OnceLock::new();
+
+/// Remembers whether `--no-pager` was given, for the `page` helper used
+/// throughout the program. Should be called once, early in `main`.
+pub fn init(disabled: bool) {
+    let _ = DISABLED.set(disabled);
+}
+
+/// Writes `content` to standard output, paging it through `$PAGER` (falling
+/// back to `less -R`) if standard output is a terminal and `--no-pager` was
+/// not given; otherwise prints it directly, the way piping to another
+/// command expects.
+pub fn page(content: &str) -> Result<(), anyhow::Error> {
+    let disabled = DISABLED.get().copied().unwrap_or(false);
+
+    if disabled || !std::io::stdout().is_terminal() {
+        print!("{}", content);
+        return Ok(());
+    }
+
+    highlight::page(content)
+}
@@ -0,0 +1,37 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A stable, tab-separated machine output format used by `--porcelain`
+//! flags across subcommands, so scripts do not break when the human-facing
+//! output is improved.
+//!
+//! Every line is versioned so that a future incompatible change can bump
+//! [`VERSION`] rather than breaking existing scripts silently.
+
+/// The current porcelain format version.
+pub const VERSION: u32 = 1;
+
+/// Formats a single porcelain line: the format version followed by
+/// tab-separated fields.
+pub fn line(fields: &[&str]) -> String {
+    let mut line = format!("v{}", VERSION);
+    for field in fields {
+        line.push('\t');
+        line.push_str(field);
+    }
+    line
+}
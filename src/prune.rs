@@ -0,0 +1,84 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Opportunistic pruning of the local history log, run once a day at the
+//! start of any command, so it doesn't grow unbounded over the life of a
+//! machine.
+//!
+//! The archive ([`crate::archive`]) is deliberately left untouched here:
+//! its whole purpose is to keep a paste's content retrievable (see `cat`'s
+//! fallback to it) after the paste has expired on pastery.net, so pruning
+//! it by expiry would defeat the feature.
+
+use std::fs;
+use std::time::Duration;
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::history;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn marker_path() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("last_prune")).ok()
+}
+
+/// Prunes expired history entries, unless this has already run within the
+/// last [`PRUNE_INTERVAL`], so a normal invocation of patisserie doesn't pay
+/// the cost of rewriting the history file every time.
+pub fn maybe_prune() -> Result<(), anyhow::Error> {
+    let Some(marker_path) = marker_path() else {
+        return Ok(());
+    };
+
+    let ran_recently = fs::metadata(&marker_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < PRUNE_INTERVAL);
+
+    if ran_recently {
+        return Ok(());
+    }
+
+    prune_history()?;
+
+    if let Some(dir) = marker_path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create directory `{}'", dir))?;
+    }
+    fs::write(&marker_path, "")
+        .with_context(|| format!("Could not write file `{}'", marker_path))?;
+
+    Ok(())
+}
+
+fn prune_history() -> Result<(), anyhow::Error> {
+    let now = OffsetDateTime::now_utc();
+
+    history::retain(|entry| {
+        entry
+            .expires_at
+            .as_deref()
+            .and_then(|expires_at| OffsetDateTime::parse(expires_at, &Rfc3339).ok())
+            .is_none_or(|expires_at| expires_at > now)
+    })
+}
@@ -0,0 +1,101 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A local spool for pastes that could not be uploaded because the network
+//! was unavailable, flushed later with `patisserie flush`.
+
+use std::fs;
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use patisserie::api::NewPaste;
+
+/// A paste that was spooled to the offline queue instead of being uploaded.
+#[derive(Serialize, Deserialize)]
+pub struct QueuedPaste {
+    pub body: String,
+    pub duration: u32,
+    pub language: String,
+    pub title: Option<String>,
+    pub max_views: Option<u32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl QueuedPaste {
+    /// Borrows this queued paste as the options `PasteryClient::create` expects.
+    pub fn as_new_paste(&self) -> NewPaste<'_> {
+        NewPaste {
+            duration: self.duration,
+            language: &self.language,
+            title: self.title.clone(),
+            max_views: self.max_views,
+        }
+    }
+}
+
+fn dir() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("queue")).ok()
+}
+
+/// Spools `paste` to the offline queue, returning the path it was written to.
+pub fn enqueue(paste: &QueuedPaste) -> Result<Utf8PathBuf, anyhow::Error> {
+    let dir = dir().context("Could not determine a data directory for the offline queue")?;
+    fs::create_dir_all(&dir).with_context(|| format!("Could not create directory `{}'", dir))?;
+
+    let filename = format!("{}.json", OffsetDateTime::now_utc().unix_timestamp_nanos());
+    let path = dir.join(filename);
+
+    let contents = serde_json::to_string_pretty(paste).context("Could not serialize paste")?;
+    fs::write(&path, contents).with_context(|| format!("Could not write file `{}'", path))?;
+
+    Ok(path)
+}
+
+/// Lists every queued paste, oldest first.
+pub fn list() -> Result<Vec<Utf8PathBuf>, anyhow::Error> {
+    let Some(dir) = dir() else {
+        return Ok(Vec::new());
+    };
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Could not read directory `{}'", dir)),
+    };
+
+    let mut paths: Vec<Utf8PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Utf8PathBuf::from_path_buf(entry.path()).ok())
+        .filter(|path| path.extension() == Some("json"))
+        .collect();
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Reads a single queued paste from `path`.
+pub fn read(path: &Utf8PathBuf) -> Result<QueuedPaste, anyhow::Error> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Could not read file `{}'", path))?;
+    serde_json::from_str(&contents).with_context(|| format!("Could not parse file `{}'", path))
+}
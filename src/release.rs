@@ -0,0 +1,111 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Checking for a newer patisserie release on GitHub, for `patisserie
+//! version --check` and the opt-in passive check run at the start of every
+//! command (see the `check_for_updates` config setting).
+
+use std::fs;
+use std::time::Duration;
+
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use directories::ProjectDirs;
+use reqwest::header::USER_AGENT;
+use semver::Version;
+use serde::Deserialize;
+
+const RELEASES_URL: &str =
+    "https://api.github.com/repos/freshstrangemusic/patisserie/releases/latest";
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+/// Fetches the version of the latest GitHub release, stripping a leading
+/// `v` from its tag name, if present (e.g. `v1.2.3` -> `1.2.3`).
+pub fn latest_version() -> Result<String, anyhow::Error> {
+    let release: Release = reqwest::blocking::Client::new()
+        .get(RELEASES_URL)
+        .header(USER_AGENT, "patisserie")
+        .send()
+        .context("Could not check for a new release")?
+        .error_for_status()
+        .context("Could not check for a new release")?
+        .json()
+        .context("Could not parse the GitHub releases response")?;
+
+    Ok(release.tag_name.trim_start_matches('v').to_owned())
+}
+
+/// Whether `latest` is a newer version than `current`.
+pub fn is_newer(latest: &str, current: &str) -> Result<bool, anyhow::Error> {
+    let latest =
+        Version::parse(latest).with_context(|| format!("Could not parse version `{}'", latest))?;
+    let current = Version::parse(current)
+        .with_context(|| format!("Could not parse version `{}'", current))?;
+
+    Ok(latest > current)
+}
+
+fn marker_path() -> Option<Utf8PathBuf> {
+    let dirs = ProjectDirs::from("", "", "patisserie")?;
+    Utf8PathBuf::from_path_buf(dirs.data_dir().join("last_update_check")).ok()
+}
+
+/// Passively checks for a newer release at most once a day, printing a
+/// one-line note to standard error if one is available.
+///
+/// This is opt-in (see the `check_for_updates` config setting) and
+/// best-effort: any failure, including simply being offline, is ignored
+/// rather than reported, since it should never get in the way of the
+/// command actually being run.
+pub fn maybe_check_passively() {
+    let Some(marker_path) = marker_path() else {
+        return;
+    };
+
+    let checked_recently = fs::metadata(&marker_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CHECK_INTERVAL);
+
+    if checked_recently {
+        return;
+    }
+
+    if let Some(dir) = marker_path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(&marker_path, "");
+
+    let current = env!("CARGO_PKG_VERSION");
+    if let Ok(latest) = latest_version()
+        && is_newer(&latest, current).unwrap_or(false)
+    {
+        eprintln!(
+            "{}",
+            crate::color::warning(&format!(
+                "A newer version of patisserie is available: {} (you have {})",
+                latest, current
+            ))
+        );
+    }
+}
@@ -0,0 +1,38 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Building a [`Command`] that runs a user-supplied shell command string, so
+//! config- and flag-provided commands can use pipes and redirections the
+//! way they would on the command line.
+
+use std::process::Command;
+
+/// Returns an unspawned [`Command`] that runs `command` through the
+/// platform shell.
+#[cfg(not(windows))]
+pub fn command(command_line: &str) -> Command {
+    let mut process = Command::new("sh");
+    process.args(["-c", command_line]);
+    process
+}
+
+#[cfg(windows)]
+pub fn command(command_line: &str) -> Command {
+    let mut process = Command::new("cmd");
+    process.args(["/C", command_line]);
+    process
+}
@@ -0,0 +1,39 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Posts a paste's link to a Slack channel via an incoming webhook, so a
+//! team can see shared pastes without polling the pastery.net API.
+
+use anyhow::Context;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    channel: &'a str,
+    text: &'a str,
+}
+
+/// Posts `text` to `channel` via the incoming webhook at `webhook_url`.
+pub fn notify(webhook_url: &str, channel: &str, text: &str) -> Result<(), anyhow::Error> {
+    reqwest::blocking::Client::new()
+        .post(webhook_url)
+        .json(&Payload { channel, text })
+        .send()
+        .with_context(|| format!("Could not notify Slack channel `{}'", channel))?;
+
+    Ok(())
+}
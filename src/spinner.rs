@@ -0,0 +1,94 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A small terminal spinner shown on standard error while a request is in
+//! flight, so a slow upload doesn't look like the tool has hung.
+
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const FRAME_INTERVAL: Duration = Duration::from_millis(80);
+
+/// A running spinner, stopped by dropping it. Does nothing if standard
+/// error isn't a terminal.
+pub struct Spinner {
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+    message_len: usize,
+}
+
+impl Spinner {
+    /// Starts a spinner showing `message` on standard error.
+    pub fn start(message: &str) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = std::io::stderr().is_terminal().then(|| {
+            let running = Arc::clone(&running);
+            let message = message.to_owned();
+            std::thread::spawn(move || {
+                let mut stderr = std::io::stderr();
+                let mut frame = 0;
+                while running.load(Ordering::Relaxed) {
+                    let _ = write!(stderr, "\r{} {}", FRAMES[frame % FRAMES.len()], message);
+                    let _ = stderr.flush();
+                    frame += 1;
+                    std::thread::sleep(FRAME_INTERVAL);
+                }
+            })
+        });
+
+        Self {
+            running,
+            handle,
+            message_len: message.chars().count(),
+        }
+    }
+
+    /// Formats a byte count the way the spinner's upload message does, e.g.
+    /// `48 KiB`.
+    pub fn format_size(bytes: usize) -> String {
+        const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+
+        let mut size = bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            format!("{} {}", bytes, UNITS[unit])
+        } else {
+            format!("{:.1} {}", size, UNITS[unit])
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+            eprint!("\r{}\r", " ".repeat(self.message_len + 2));
+            let _ = std::io::stderr().flush();
+        }
+    }
+}
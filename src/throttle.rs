@@ -0,0 +1,61 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A client-side throttle that spaces out calls to [`Throttle::wait`] so
+/// that batch operations don't trip pastery.net's server-side rate limit in
+/// the first place.
+pub struct Throttle {
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Throttle {
+    /// Creates a throttle allowing at most `requests_per_minute` calls to
+    /// [`Throttle::wait`] per minute, or no throttling at all if
+    /// `requests_per_minute` is `None` or `0`.
+    pub fn new(requests_per_minute: Option<u32>) -> Self {
+        let interval = requests_per_minute
+            .filter(|&rpm| rpm > 0)
+            .map(|rpm| Duration::from_secs_f64(60.0 / f64::from(rpm)))
+            .unwrap_or_default();
+
+        Self {
+            interval,
+            last_request: None,
+        }
+    }
+
+    /// Blocks until at least one throttle interval has passed since the
+    /// previous call to `wait`, if throttling is enabled.
+    pub fn wait(&mut self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        if let Some(last_request) = self.last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        self.last_request = Some(Instant::now());
+    }
+}
@@ -0,0 +1,108 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A DNS/connect/TLS/transfer timing breakdown for `--timings`, printed to
+//! standard error to help diagnose "why is pasting slow from this host".
+//!
+//! DNS, connect, and TLS are measured with a short-lived probe connection
+//! made just before the real upload, since [`PasteryClient`](patisserie::api::PasteryClient)'s
+//! pooled connection does not expose per-phase timings for the request it
+//! actually sends. Transfer time is measured around the real upload call by
+//! the caller.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+
+const PASTERY_HOST: &str = "www.pastery.net";
+const HTTPS_PORT: u16 = 443;
+
+/// A DNS/connect/TLS/transfer timing breakdown.
+pub struct Timings {
+    pub dns: Duration,
+    pub connect: Duration,
+    pub tls: Duration,
+    pub transfer: Duration,
+    pub payload_bytes: usize,
+}
+
+/// Times DNS resolution, TCP connect, and TLS handshake against
+/// pastery.net with a fresh probe connection, then calls `upload` and times
+/// it as the transfer phase.
+pub fn measure<T>(
+    payload_bytes: usize,
+    upload: impl FnOnce() -> T,
+) -> Result<(T, Timings), anyhow::Error> {
+    let (dns, connect, tls) = probe()?;
+
+    let transfer_start = Instant::now();
+    let result = upload();
+    let transfer = transfer_start.elapsed();
+
+    Ok((
+        result,
+        Timings {
+            dns,
+            connect,
+            tls,
+            transfer,
+            payload_bytes,
+        },
+    ))
+}
+
+/// Probes DNS resolution, TCP connect, and TLS handshake time against
+/// pastery.net.
+fn probe() -> Result<(Duration, Duration, Duration), anyhow::Error> {
+    let dns_start = Instant::now();
+    let addr = (PASTERY_HOST, HTTPS_PORT)
+        .to_socket_addrs()
+        .context("Could not resolve pastery.net")?
+        .next()
+        .ok_or_else(|| anyhow!("Could not resolve pastery.net"))?;
+    let dns = dns_start.elapsed();
+
+    let connect_start = Instant::now();
+    let stream = TcpStream::connect(addr).context("Could not connect to pastery.net")?;
+    let connect = connect_start.elapsed();
+
+    let tls_start = Instant::now();
+    let connector = native_tls::TlsConnector::new().context("Could not initialize TLS")?;
+    connector
+        .connect(PASTERY_HOST, stream)
+        .map_err(|err| anyhow!("Could not complete TLS handshake with pastery.net: {}", err))?;
+    let tls = tls_start.elapsed();
+
+    Ok((dns, connect, tls))
+}
+
+/// Formats a duration in milliseconds, e.g. `42ms`.
+fn format_ms(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Timings:")?;
+        writeln!(f, "  DNS:      {}", format_ms(self.dns))?;
+        writeln!(f, "  Connect:  {}", format_ms(self.connect))?;
+        writeln!(f, "  TLS:      {}", format_ms(self.tls))?;
+        writeln!(f, "  Transfer: {}", format_ms(self.transfer))?;
+        write!(f, "  Payload:  {} bytes", self.payload_bytes)
+    }
+}
@@ -0,0 +1,97 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A VCR-style record/replay transport for `--record`/`--replay`, letting
+//! integration tests of patisserie itself (or of scripts that wrap it) run
+//! against canned API interactions instead of the real network.
+//!
+//! A cassette is a file of one JSON [`Interaction`] per line, in the order
+//! the requests were made; replay consumes them in that same order.
+
+use reqwest::Method;
+use reqwest::blocking::Response as HttpResponse;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    method: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// Parses a cassette file's contents (one JSON [`Interaction`] per line),
+/// for `--replay`.
+pub fn parse_cassette(contents: &str) -> Result<Vec<Interaction>, serde_json::Error> {
+    contents.lines().map(serde_json::from_str).collect()
+}
+
+/// Serializes `interaction` as one cassette line, for `--record`.
+pub fn serialize_interaction(interaction: &Interaction) -> String {
+    serde_json::to_string(interaction).expect("an Interaction always serializes")
+}
+
+/// Captures a completed live request/response as an [`Interaction`].
+pub fn capture(method: &Method, url: &reqwest::Url, status: u16, headers: &HeaderMap, body: &[u8]) -> Interaction {
+    Interaction {
+        method: method.to_string(),
+        url: url.to_string(),
+        status,
+        headers: headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect(),
+        body: String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+/// Rebuilds an [`HttpResponse`] from an [`Interaction`], so replay and
+/// record both feed the same response-parsing path as a live request.
+pub fn to_http_response(interaction: &Interaction) -> HttpResponse {
+    let mut builder = http::Response::builder().status(interaction.status);
+    for (name, value) in &interaction.headers {
+        builder = builder.header(name, value);
+    }
+
+    let response = builder
+        .body(interaction.body.clone().into_bytes())
+        .expect("a recorded response's status and headers are always valid");
+
+    HttpResponse::from(response)
+}
+
+/// Finds and removes the next interaction matching `method` and `url` from
+/// a replay cassette, in recording order, so a cassette replays
+/// deterministically even if several interactions share a URL.
+pub fn next_match(
+    cassette: &mut std::collections::VecDeque<Interaction>,
+    method: &Method,
+    url: &reqwest::Url,
+) -> Option<Interaction> {
+    let position = cassette
+        .iter()
+        .position(|interaction| interaction.method == method.as_str() && interaction.url == url.as_str())?;
+    cassette.remove(position)
+}
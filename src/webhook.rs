@@ -0,0 +1,43 @@
+/* patisserie - A CLI for pastery.net
+ * Copyright (C) 2025  Beth Rennie
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A post-upload webhook notification, so team dashboards or bots can track
+//! shared pastes without polling the pastery.net API.
+
+use anyhow::Context;
+use serde::Serialize;
+
+/// The JSON payload POSTed to a configured webhook after a successful
+/// upload.
+#[derive(Serialize)]
+pub struct Notification<'a> {
+    pub url: &'a str,
+    pub title: Option<&'a str>,
+    pub language: &'a str,
+    pub expiry: &'a str,
+}
+
+/// POSTs `notification` as JSON to `webhook_url`.
+pub fn notify(webhook_url: &str, notification: &Notification<'_>) -> Result<(), anyhow::Error> {
+    reqwest::blocking::Client::new()
+        .post(webhook_url)
+        .json(notification)
+        .send()
+        .with_context(|| format!("Could not notify webhook `{}'", webhook_url))?;
+
+    Ok(())
+}